@@ -0,0 +1,109 @@
+use claude::batch::Batch;
+use claude::decimal::Decimal;
+use claude::rounding::RoundingMode;
+use claude::Currency;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const SIZES: [usize; 3] = [1_000, 100_000, 1_000_000];
+
+fn values(len: usize) -> Vec<i64> {
+    (0..len as i64)
+        .map(|i| (i * 7919) % 100_000 - 50_000)
+        .collect()
+}
+
+fn currencies(values: &[i64]) -> Vec<Currency> {
+    values
+        .iter()
+        .map(|&value| Currency::from(('€', value)))
+        .collect()
+}
+
+fn sum(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sum");
+    for &len in &SIZES {
+        let values = values(len);
+        let currencies = currencies(&values);
+        group.bench_with_input(BenchmarkId::new("iterator", len), &currencies, |b, list| {
+            b.iter(|| black_box(list).iter().sum::<Currency>())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("currencies", len),
+            &currencies,
+            |b, list| b.iter(|| Batch::from_currencies(black_box(list)).unwrap().sum()),
+        );
+        group.bench_with_input(BenchmarkId::new("values", len), &values, |b, values| {
+            b.iter(|| Batch::from_values(Some('€'), black_box(values)).sum())
+        });
+    }
+    group.finish();
+}
+
+fn checked_sum(c: &mut Criterion) {
+    let mut group = c.benchmark_group("checked_sum");
+    for &len in &SIZES {
+        let values = values(len);
+        let currencies = currencies(&values);
+        group.bench_with_input(BenchmarkId::new("iterator", len), &currencies, |b, list| {
+            b.iter(|| {
+                let (first, rest) = black_box(list).split_first().unwrap();
+                rest.iter()
+                    .try_fold(*first, |acc, money| acc.checked_add(*money))
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("values", len), &values, |b, values| {
+            b.iter(|| Batch::from_values(Some('€'), black_box(values)).checked_sum())
+        });
+    }
+    group.finish();
+}
+
+fn max(c: &mut Criterion) {
+    let mut group = c.benchmark_group("max");
+    for &len in &SIZES {
+        let values = values(len);
+        let currencies = currencies(&values);
+        group.bench_with_input(BenchmarkId::new("iterator", len), &currencies, |b, list| {
+            b.iter(|| {
+                black_box(list)
+                    .iter()
+                    .max_by_key(|money| money.value)
+                    .copied()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("values", len), &values, |b, values| {
+            b.iter(|| Batch::from_values(Some('€'), black_box(values)).max())
+        });
+    }
+    group.finish();
+}
+
+fn scale(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scale");
+    let rate = Decimal::new(1075, 3);
+    let len = 100_000;
+    let values = values(len);
+    let currencies = currencies(&values);
+    group.bench_with_input(BenchmarkId::new("iterator", len), &currencies, |b, list| {
+        b.iter(|| {
+            black_box(list)
+                .iter()
+                .map(|money| {
+                    Decimal::new(i128::from(money.value), 0)
+                        .checked_mul(&rate)?
+                        .rescale(0, RoundingMode::HalfUp)
+                        .map(|scaled| Currency::from(('€', scaled.mantissa() as i64)))
+                })
+                .collect::<Option<Vec<_>>>()
+        })
+    });
+    group.bench_with_input(BenchmarkId::new("values", len), &values, |b, values| {
+        b.iter(|| {
+            Batch::from_values(Some('€'), black_box(values)).scale(rate, RoundingMode::HalfUp)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, sum, checked_sum, max, scale);
+criterion_main!(benches);
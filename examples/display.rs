@@ -7,7 +7,9 @@ fn main() {
     };
 
     println!("I have {:?}!", my_money);
-    //println!("I have {}!", my_money); // doesn't compile :D
+    println!("I have {}!", my_money); // -> "I have €47.11!"
     println!("I have {}!", my_money.postfix()); // -> "I have 47,11€!"
     println!("I have {}!", my_money.prefix()); // -> "I have €47.11!"
+    println!("I have {:>10}!", my_money.prefix()); // -> "I have     €47.11!"
+    println!("I have {:+.0}!", my_money); // -> "I have +€47!"
 }
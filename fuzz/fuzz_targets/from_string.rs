@@ -0,0 +1,8 @@
+#![no_main]
+
+use claude::Currency;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    let _ = Currency::from_string(s);
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use claude::locale::Locale;
+use claude::Currency;
+use libfuzzer_sys::fuzz_target;
+
+const LOCALES: [Locale; 5] = [
+    Locale::EnUs,
+    Locale::EnGb,
+    Locale::EnIn,
+    Locale::DeDe,
+    Locale::DeCh,
+];
+
+fuzz_target!(|s: &str| {
+    let _ = s.parse::<Currency>();
+    for &locale in &LOCALES {
+        let _ = Currency::from_str_with_locale(s, locale);
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use claude::Currency;
+use libfuzzer_sys::fuzz_target;
+
+// Whatever parses has to survive formatting and parsing again unchanged.
+fuzz_target!(|s: &str| {
+    if let Ok(money) = s.parse::<Currency>() {
+        assert_eq!(money.to_string().parse::<Currency>(), Ok(money));
+        assert_eq!(money.postfix().to_string().parse::<Currency>(), Ok(money));
+        assert_eq!(Currency::from_string(&money.to_string()), Some(money));
+    }
+});
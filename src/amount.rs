@@ -0,0 +1,84 @@
+//! Unit-less amounts.
+//!
+//! An [`Amount`] is a number of hundredths without a currency. It combines with a `Currency` of
+//! any symbol and takes on that symbol:
+//!
+//! * `Currency + Currency` requires both symbols to be equal, a `Currency` without symbol is just
+//!   another currency and does not mix with `$` or `€`
+//! * `Currency + Amount` and `Amount + Currency` always work and keep the currency's symbol
+//! * `Amount + Amount` is an `Amount`
+//!
+//! The same goes for `-`. Equality follows the same model, `Currency == Currency` compares symbol
+//! and value, while `Currency == Amount` only compares the value.
+
+use std::fmt;
+
+use crate::{Currency, Symbol};
+
+/// A number of hundredths without a currency symbol.
+///
+/// # Examples
+/// ```
+/// use claude::{amount::Amount, Currency};
+///
+/// let price = Currency::from(('€', 1000));
+/// let fee = Amount(250);
+///
+/// assert_eq!(price + fee, Currency::from(('€', 1250)));
+/// assert_eq!(fee + price, Currency::from(('€', 1250)));
+/// assert_eq!(price, Amount(1000));
+/// assert_ne!(price, Currency::from(1000));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize))]
+pub struct Amount(pub i64);
+
+impl Amount {
+    /// Returns the inner value
+    pub fn value(self) -> i64 {
+        self.0
+    }
+
+    /// Attaches a currency symbol.
+    pub fn with_symbol(self, symbol: Symbol) -> Currency {
+        Currency {
+            symbol: Some(symbol),
+            value: self.0,
+        }
+    }
+}
+
+impl From<i64> for Amount {
+    /// converts from a `i64`
+    fn from(value: i64) -> Amount {
+        Amount(value)
+    }
+}
+
+impl From<Currency> for Amount {
+    /// drops the symbol
+    fn from(money: Currency) -> Amount {
+        Amount(money.value)
+    }
+}
+
+/// Compares the value only, regardless of the symbol.
+impl PartialEq<Amount> for Currency {
+    fn eq(&self, other: &Amount) -> bool {
+        self.value == other.0
+    }
+}
+
+/// Compares the value only, regardless of the symbol.
+impl PartialEq<Currency> for Amount {
+    fn eq(&self, other: &Currency) -> bool {
+        self.0 == other.value
+    }
+}
+
+/// Formats like a `Currency` without symbol, e.g. `12.10`
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Currency::from_value(self.0), f)
+    }
+}
@@ -0,0 +1,516 @@
+//! Conversion to and from [Apache Arrow](https://arrow.apache.org) arrays, with the `arrow`
+//! feature.
+//!
+//! Amounts of one currency become a `Decimal128` array with scale 2. The currency is kept in the
+//! metadata of the field under [`CURRENCY_CODE`]. Amounts of mixed currencies become a struct
+//! array with a [`CODE`] and a [`MINOR_UNITS`] column.
+//!
+//! Codes are ISO 4217 codes where the symbol is known (see [`CurrencyInfo`]), otherwise the
+//! symbol itself. Amounts without a symbol have no code.
+//!
+//! # Examples
+//! ```
+//! use claude::{arrow, Currency};
+//!
+//! let ledger = [Currency::from(('€', 1250)), Currency::from(('€', -300))];
+//! let (field, array) = arrow::to_decimal128("amount", &ledger).unwrap();
+//! assert_eq!(field.metadata()[arrow::CURRENCY_CODE], "EUR");
+//!
+//! let batch = arrow::decimal128_batch(&field, &array).unwrap();
+//! assert_eq!(batch.sum(), Currency::from(('€', 950)));
+//! assert_eq!(arrow::from_decimal128(&field, &array), Ok(ledger.to_vec()));
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow_array::{Array, ArrayRef, Decimal128Array, Int64Array, StringArray, StructArray};
+use arrow_schema::{DataType, Field};
+
+use crate::batch::Batch;
+use crate::column::CurrencyColumn;
+use crate::info::CurrencyInfo;
+use crate::{Currency, Symbol};
+
+/// Key of the field metadata holding the currency code of a `Decimal128` array.
+pub const CURRENCY_CODE: &str = "currency_code";
+/// Precision of the `Decimal128` arrays created here, enough for every `i64`.
+pub const DECIMAL_PRECISION: u8 = 19;
+/// Scale of the `Decimal128` arrays created here.
+pub const DECIMAL_SCALE: i8 = 2;
+/// Name of the `Utf8` column holding the currency code in a struct array.
+pub const CODE: &str = "code";
+/// Name of the `Int64` column holding the value in the smallest unit in a struct array.
+pub const MINOR_UNITS: &str = "minor_units";
+
+/// Reasons why an Arrow array cannot be converted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArrowError {
+    /// The amounts have different currencies.
+    CurrencyMismatch,
+    /// A currency code is neither a known ISO 4217 code with a symbol nor a single symbol.
+    UnknownCurrency,
+    /// The array contains nulls.
+    Null,
+    /// A struct array lacks the [`CODE`] or [`MINOR_UNITS`] column, or they have the wrong type.
+    InvalidType,
+    /// The buffer of a `Decimal128` array can only be used directly with scale 2.
+    InvalidScale,
+    /// A value has more fractional digits than the smallest unit.
+    Inexact,
+    /// A value does not fit into an `i64`.
+    Overflow,
+}
+
+impl fmt::Display for ArrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ArrowError::CurrencyMismatch => "all amounts must share a currency",
+            ArrowError::UnknownCurrency => "unknown currency code",
+            ArrowError::Null => "arrays of amounts must not contain nulls",
+            ArrowError::InvalidType => "not a struct array of currency codes and minor units",
+            ArrowError::InvalidScale => "decimal buffer must have a scale of 2",
+            ArrowError::Inexact => "value has fractions of the smallest unit",
+            ArrowError::Overflow => "value does not fit",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for ArrowError {}
+
+fn code(symbol: Option<Symbol>) -> Option<String> {
+    let symbol = symbol?;
+    Some(match CurrencyInfo::from_symbol(symbol) {
+        Some(info) => info.code().to_string(),
+        None => symbol.to_string(),
+    })
+}
+
+fn symbol(code: &str) -> Result<Option<Symbol>, ArrowError> {
+    if let Some(info) = CurrencyInfo::from_code(code) {
+        // currencies without a symbol cannot be told apart from amounts without a currency
+        return info.symbol().map(Some).ok_or(ArrowError::UnknownCurrency);
+    }
+    let mut chars = code.chars();
+    match (chars.next(), chars.next()) {
+        (Some(symbol), None) => Ok(Some(symbol)),
+        _ => Err(ArrowError::UnknownCurrency),
+    }
+}
+
+fn field_symbol(field: &Field) -> Result<Option<Symbol>, ArrowError> {
+    match field.metadata().get(CURRENCY_CODE) {
+        Some(code) => symbol(code),
+        None => Ok(None),
+    }
+}
+
+/// Converts `value` with `scale` fractional digits to the smallest unit.
+fn minor_units(value: i128, scale: i8) -> Result<i64, ArrowError> {
+    if value == 0 {
+        return Ok(0);
+    }
+    let scale = i32::from(scale) - i32::from(DECIMAL_SCALE);
+    let factor = 10i128.checked_pow(scale.unsigned_abs());
+    let value = if scale > 0 {
+        // a divisor beyond `i128` leaves a non-zero value with a fraction
+        let divisor = factor.ok_or(ArrowError::Inexact)?;
+        if value % divisor != 0 {
+            return Err(ArrowError::Inexact);
+        }
+        value / divisor
+    } else {
+        factor
+            .and_then(|factor| factor.checked_mul(value))
+            .ok_or(ArrowError::Overflow)?
+    };
+    i64::try_from(value).map_err(|_| ArrowError::Overflow)
+}
+
+/// A non-nullable `Decimal128` field named `name`, carrying the code of `symbol` in its metadata.
+pub fn decimal_field(name: &str, symbol: Option<Symbol>) -> Field {
+    let field = Field::new(
+        name,
+        DataType::Decimal128(DECIMAL_PRECISION, DECIMAL_SCALE),
+        false,
+    );
+    match code(symbol) {
+        Some(code) => field.with_metadata(HashMap::from([(CURRENCY_CODE.to_string(), code)])),
+        None => field,
+    }
+}
+
+/// Converts amounts of one currency to a `Decimal128` array and its field.
+///
+/// An empty slice makes an empty array without a currency code.
+pub fn to_decimal128(
+    name: &str,
+    amounts: &[Currency],
+) -> Result<(Field, Decimal128Array), ArrowError> {
+    let batch = Batch::from_currencies(amounts).map_err(|_| ArrowError::CurrencyMismatch)?;
+    let array = decimal_array(amounts.iter().map(|money| money.value));
+    Ok((decimal_field(name, batch.symbol()), array))
+}
+
+/// Converts a `Decimal128` array back, with the currency taken from the metadata of `field`.
+///
+/// Arrays of any scale are accepted as long as no value has fractions of the smallest unit.
+pub fn from_decimal128(
+    field: &Field,
+    array: &Decimal128Array,
+) -> Result<Vec<Currency>, ArrowError> {
+    CurrencyColumn::from_decimal128(field, array).map(Vec::from)
+}
+
+fn decimal_array<I>(values: I) -> Decimal128Array
+where
+    I: Iterator<Item = i64>,
+{
+    Decimal128Array::from_iter_values(values.map(i128::from))
+        .with_precision_and_scale(DECIMAL_PRECISION, DECIMAL_SCALE)
+        .expect("valid decimal precision and scale")
+}
+
+/// Borrows the buffer of a `Decimal128` array for the operations of [`Batch`], without copying.
+///
+/// The array must have scale 2 and no nulls.
+pub fn decimal128_batch<'a>(
+    field: &Field,
+    array: &'a Decimal128Array,
+) -> Result<Batch<'a>, ArrowError> {
+    if array.scale() != DECIMAL_SCALE {
+        return Err(ArrowError::InvalidScale);
+    }
+    if array.null_count() > 0 {
+        return Err(ArrowError::Null);
+    }
+    Batch::from_wide(field_symbol(field)?, array.values()).ok_or(ArrowError::Overflow)
+}
+
+/// Converts amounts of any currencies to a struct array of [`CODE`] and [`MINOR_UNITS`].
+///
+/// # Examples
+/// ```
+/// use claude::{arrow, Currency};
+///
+/// let amounts = [Currency::from(('$', 100)), Currency::from(('₿', 5)), Currency::from(7)];
+/// let array = arrow::to_struct(&amounts);
+/// assert_eq!(array.num_columns(), 2);
+/// assert_eq!(arrow::from_struct(&array), Ok(amounts.to_vec()));
+/// ```
+pub fn to_struct(amounts: &[Currency]) -> StructArray {
+    let codes: StringArray = amounts.iter().map(|money| code(money.symbol)).collect();
+    let codes: ArrayRef = Arc::new(codes);
+    let values: ArrayRef = Arc::new(Int64Array::from_iter_values(
+        amounts.iter().map(|money| money.value),
+    ));
+    StructArray::from(vec![
+        (Arc::new(Field::new(CODE, DataType::Utf8, true)), codes),
+        (
+            Arc::new(Field::new(MINOR_UNITS, DataType::Int64, false)),
+            values,
+        ),
+    ])
+}
+
+fn struct_columns(array: &StructArray) -> Result<(&StringArray, &Int64Array), ArrowError> {
+    let codes = array
+        .column_by_name(CODE)
+        .and_then(|codes| codes.as_any().downcast_ref::<StringArray>())
+        .ok_or(ArrowError::InvalidType)?;
+    let values = array
+        .column_by_name(MINOR_UNITS)
+        .and_then(|values| values.as_any().downcast_ref::<Int64Array>())
+        .ok_or(ArrowError::InvalidType)?;
+    if array.null_count() > 0 || values.null_count() > 0 {
+        return Err(ArrowError::Null);
+    }
+    Ok((codes, values))
+}
+
+/// Converts a struct array of [`CODE`] and [`MINOR_UNITS`] back, a null code means no symbol.
+pub fn from_struct(array: &StructArray) -> Result<Vec<Currency>, ArrowError> {
+    let (codes, values) = struct_columns(array)?;
+    let mut amounts = Vec::with_capacity(array.len());
+    // codes mostly repeat, so only look up changes
+    let mut last: Option<(Option<&str>, Option<Symbol>)> = None;
+    for (code, &value) in codes.iter().zip(values.values().iter()) {
+        let symbol = match last {
+            Some((last_code, symbol)) if last_code == code => symbol,
+            _ => {
+                let symbol = code.map(symbol).transpose()?.flatten();
+                last = Some((code, symbol));
+                symbol
+            }
+        };
+        amounts.push(Currency { symbol, value });
+    }
+    Ok(amounts)
+}
+
+/// Borrows the [`MINOR_UNITS`] buffer of a struct array for the operations of [`Batch`], without
+/// copying.
+///
+/// All rows must have the same code and no nulls.
+///
+/// # Examples
+/// ```
+/// use claude::{arrow, Currency};
+///
+/// let array = arrow::to_struct(&[Currency::from(('£', 500)), Currency::from(('£', 75))]);
+/// let batch = arrow::struct_batch(&array).unwrap();
+/// assert_eq!(batch.max(), Some(Currency::from(('£', 500))));
+/// ```
+pub fn struct_batch(array: &StructArray) -> Result<Batch<'_>, ArrowError> {
+    let (codes, values) = struct_columns(array)?;
+    let first = codes.iter().next().flatten();
+    if codes.iter().any(|code| code != first) {
+        return Err(ArrowError::CurrencyMismatch);
+    }
+    let symbol = first.map(symbol).transpose()?.flatten();
+    Ok(Batch::from_values(symbol, values.values()))
+}
+
+/// Arrow conversion.
+impl CurrencyColumn {
+    /// Converts to a `Decimal128` array and its field, see [`to_decimal128`].
+    pub fn to_decimal128(&self, name: &str) -> (Field, Decimal128Array) {
+        let array = decimal_array(self.values().iter().copied());
+        (decimal_field(name, self.symbol()), array)
+    }
+
+    /// Converts a `Decimal128` array back, see [`from_decimal128`].
+    pub fn from_decimal128(
+        field: &Field,
+        array: &Decimal128Array,
+    ) -> Result<CurrencyColumn, ArrowError> {
+        if array.null_count() > 0 {
+            return Err(ArrowError::Null);
+        }
+        let scale = array.scale();
+        let values = array
+            .values()
+            .iter()
+            .map(|&value| minor_units(value, scale))
+            .collect::<Result<Vec<i64>, ArrowError>>()?;
+        Ok(CurrencyColumn::from_values(field_symbol(field)?, values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn francs(value: i64) -> Currency {
+        Currency::from(('₣', value))
+    }
+
+    #[test]
+    fn decimal_round_trip() {
+        let amounts = vec![francs(100), francs(-1), francs(i64::MAX), francs(i64::MIN)];
+        let (field, array) = to_decimal128("price", &amounts).unwrap();
+        assert_eq!(field.name(), "price");
+        assert_eq!(field.metadata()[CURRENCY_CODE], "₣");
+        assert_eq!(array.precision(), DECIMAL_PRECISION);
+        assert_eq!(array.value_as_string(0), "1.00");
+        assert_eq!(array.value_as_string(1), "-0.01");
+        assert_eq!(from_decimal128(&field, &array), Ok(amounts.clone()));
+
+        let column = CurrencyColumn::try_from(amounts).unwrap();
+        let (field, array) = column.to_decimal128("price");
+        assert_eq!(CurrencyColumn::from_decimal128(&field, &array), Ok(column));
+    }
+
+    #[test]
+    fn decimal_without_symbol() {
+        let (field, array) = to_decimal128("amount", &[Currency::from(5)]).unwrap();
+        assert!(field.metadata().is_empty());
+        assert_eq!(from_decimal128(&field, &array), Ok(vec![Currency::from(5)]));
+
+        let (field, array) = to_decimal128("amount", &[]).unwrap();
+        assert!(field.metadata().is_empty());
+        assert_eq!(array.len(), 0);
+    }
+
+    #[test]
+    fn decimal_mismatch() {
+        assert_eq!(
+            to_decimal128("amount", &[francs(1), Currency::from(('€', 1))]).unwrap_err(),
+            ArrowError::CurrencyMismatch
+        );
+    }
+
+    #[test]
+    fn decimal_scales() {
+        let field = decimal_field("amount", Some('$'));
+        let array = |values: Vec<i128>, scale| {
+            Decimal128Array::from(values)
+                .with_precision_and_scale(38, scale)
+                .unwrap()
+        };
+        let dollars = |values: &[i64]| -> Vec<Currency> {
+            values
+                .iter()
+                .map(|&value| Currency::from(('$', value)))
+                .collect()
+        };
+
+        assert_eq!(
+            from_decimal128(&field, &array(vec![12_3400, -5000], 4)),
+            Ok(dollars(&[1234, -50]))
+        );
+        assert_eq!(
+            from_decimal128(&field, &array(vec![12_3456], 4)),
+            Err(ArrowError::Inexact)
+        );
+        assert_eq!(
+            from_decimal128(&field, &array(vec![12], 0)),
+            Ok(dollars(&[1200]))
+        );
+        assert_eq!(
+            from_decimal128(&field, &array(vec![5], -3)),
+            Ok(dollars(&[500_000]))
+        );
+        assert_eq!(
+            from_decimal128(&field, &array(vec![i128::from(i64::MAX)], 0)),
+            Err(ArrowError::Overflow)
+        );
+        assert_eq!(
+            from_decimal128(&field, &array(vec![i128::MAX], 1)),
+            Err(ArrowError::Overflow)
+        );
+        assert_eq!(
+            from_decimal128(&field, &array(vec![1], -50)),
+            Err(ArrowError::Overflow)
+        );
+        assert_eq!(
+            from_decimal128(&field, &array(vec![0, 0], -128)),
+            Ok(dollars(&[0, 0]))
+        );
+
+        let batch_error = decimal128_batch(&field, &array(vec![1], 3)).unwrap_err();
+        assert_eq!(batch_error, ArrowError::InvalidScale);
+    }
+
+    #[test]
+    fn unknown_code() {
+        let field = Field::new("amount", DataType::Decimal128(19, 2), false).with_metadata(
+            HashMap::from([(CURRENCY_CODE.to_string(), "XYZ".to_string())]),
+        );
+        let array = decimal_array(vec![1].into_iter());
+        assert_eq!(
+            from_decimal128(&field, &array),
+            Err(ArrowError::UnknownCurrency)
+        );
+
+        // the crate has no symbol for Swiss francs
+        let swiss = Field::new("amount", DataType::Decimal128(19, 2), false).with_metadata(
+            HashMap::from([(CURRENCY_CODE.to_string(), "CHF".to_string())]),
+        );
+        assert_eq!(
+            from_decimal128(&swiss, &array),
+            Err(ArrowError::UnknownCurrency)
+        );
+    }
+
+    #[test]
+    fn nulls() {
+        let field = decimal_field("amount", None);
+        let array = Decimal128Array::from(vec![Some(1), None])
+            .with_precision_and_scale(19, 2)
+            .unwrap();
+        assert_eq!(from_decimal128(&field, &array), Err(ArrowError::Null));
+        assert_eq!(
+            decimal128_batch(&field, &array).unwrap_err(),
+            ArrowError::Null
+        );
+
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None]));
+        let codes: ArrayRef = Arc::new(StringArray::from(vec![Some("EUR"), Some("EUR")]));
+        let array = StructArray::from(vec![
+            (Arc::new(Field::new(CODE, DataType::Utf8, true)), codes),
+            (
+                Arc::new(Field::new(MINOR_UNITS, DataType::Int64, true)),
+                values,
+            ),
+        ]);
+        assert_eq!(from_struct(&array), Err(ArrowError::Null));
+    }
+
+    #[test]
+    fn decimal_batch() {
+        let amounts: Vec<Currency> = (1..=100).map(francs).collect();
+        let (field, array) = to_decimal128("amount", &amounts).unwrap();
+        let batch = decimal128_batch(&field, &array).unwrap();
+        assert_eq!(batch.sum(), francs(5050));
+        assert_eq!(batch.min(), Some(francs(1)));
+        assert_eq!(batch.len(), 100);
+
+        let sliced = array.slice(90, 10);
+        let batch = decimal128_batch(&field, &sliced).unwrap();
+        assert_eq!(batch.sum(), francs(955));
+
+        let too_large = Decimal128Array::from(vec![i128::from(i64::MAX) + 1])
+            .with_precision_and_scale(38, 2)
+            .unwrap();
+        assert_eq!(
+            decimal128_batch(&field, &too_large).unwrap_err(),
+            ArrowError::Overflow
+        );
+    }
+
+    #[test]
+    fn struct_round_trip() {
+        let amounts = vec![
+            Currency::from(('€', 100)),
+            Currency::from(('€', 250)),
+            Currency::from(('¥', -3)),
+            Currency::from(9),
+            francs(i64::MIN),
+        ];
+        let array = to_struct(&amounts);
+        let codes = array
+            .column_by_name(CODE)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let codes: Vec<Option<&str>> = codes.iter().collect();
+        assert_eq!(
+            codes,
+            vec![Some("EUR"), Some("EUR"), Some("JPY"), None, Some("₣")]
+        );
+        assert_eq!(from_struct(&array), Ok(amounts));
+        assert_eq!(
+            struct_batch(&array).unwrap_err(),
+            ArrowError::CurrencyMismatch
+        );
+    }
+
+    #[test]
+    fn struct_batch_sum() {
+        let amounts: Vec<Currency> = (0..10).map(|value| Currency::from(('€', value))).collect();
+        let array = to_struct(&amounts);
+        assert_eq!(
+            struct_batch(&array).unwrap().sum(),
+            Currency::from(('€', 45))
+        );
+        assert_eq!(
+            struct_batch(&array.slice(8, 2)).unwrap().sum(),
+            Currency::from(('€', 17))
+        );
+    }
+
+    #[test]
+    fn struct_invalid_type() {
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![1]));
+        let array = StructArray::from(vec![(
+            Arc::new(Field::new(MINOR_UNITS, DataType::Int64, false)),
+            values,
+        )]);
+        assert_eq!(from_struct(&array), Err(ArrowError::InvalidType));
+    }
+}
@@ -0,0 +1,333 @@
+//! Bulk operations over many amounts of one currency.
+//!
+//! Summing with [`Sum`](std::iter::Sum) or folding with `+` compares the symbols of every pair of
+//! amounts, which keeps the compiler from vectorizing the loop. A [`Batch`] checks the currency
+//! once up front and then works on the plain values, either borrowed from a slice of `i64` or
+//! read from a slice of [`Currency`].
+//!
+//! # Examples
+//! ```
+//! use claude::{batch::Batch, Currency};
+//!
+//! let ledger = [1250, -300, 4999, 12];
+//! let batch = Batch::from_values(Some('€'), &ledger);
+//! assert_eq!(batch.sum(), Currency::from(('€', 5961)));
+//! assert_eq!(batch.max(), Some(Currency::from(('€', 4999))));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::decimal::{pow10, Decimal};
+use crate::rounding::{div_round, div_round_i64, RoundingMode};
+use crate::{Currency, Symbol};
+
+/// Reasons why a batch operation fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchError {
+    /// The amounts have different symbols.
+    CurrencyMismatch,
+    /// A result does not fit into an `i64`.
+    Overflow,
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            BatchError::CurrencyMismatch => "all amounts in a batch must share a currency",
+            BatchError::Overflow => "batch result does not fit",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for BatchError {}
+
+#[derive(Copy, Clone, Debug)]
+enum Values<'a> {
+    Raw(&'a [i64]),
+    // values checked to fit into an `i64`, from an Arrow `Decimal128` buffer
+    #[cfg(feature = "arrow")]
+    Wide(&'a [i128]),
+    Currencies(&'a [Currency]),
+}
+
+/// A borrowed run of amounts that all share one currency.
+#[derive(Copy, Clone, Debug)]
+pub struct Batch<'a> {
+    symbol: Option<Symbol>,
+    values: Values<'a>,
+}
+
+/// Runs `$body` with `$iter` bound to an iterator over the plain values, monomorphized for either
+/// kind of slice.
+macro_rules! with_values {
+    ($batch:expr, |$iter:ident| $body:expr) => {
+        match $batch.values {
+            Values::Raw(values) => {
+                let $iter = values.iter().copied();
+                $body
+            }
+            #[cfg(feature = "arrow")]
+            Values::Wide(values) => {
+                let $iter = values.iter().map(|&value| value as i64);
+                $body
+            }
+            Values::Currencies(currencies) => {
+                let $iter = currencies.iter().map(|money| money.value);
+                $body
+            }
+        }
+    };
+}
+
+impl<'a> Batch<'a> {
+    /// Treats `values`, given in the smallest unit, as amounts of `symbol`.
+    pub fn from_values(symbol: Option<Symbol>, values: &'a [i64]) -> Batch<'a> {
+        Batch {
+            symbol,
+            values: Values::Raw(values),
+        }
+    }
+
+    /// Treats `values` as amounts of `symbol`, `None` if any of them does not fit into an `i64`.
+    #[cfg(feature = "arrow")]
+    pub(crate) fn from_wide(symbol: Option<Symbol>, values: &'a [i128]) -> Option<Batch<'a>> {
+        let fits = |&value: &i128| i128::from(i64::MIN) <= value && value <= i128::from(i64::MAX);
+        if !values.iter().all(fits) {
+            return None;
+        }
+        Some(Batch {
+            symbol,
+            values: Values::Wide(values),
+        })
+    }
+
+    /// Checks once that all `currencies` share a symbol.
+    ///
+    /// An empty slice makes an empty batch without a symbol.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{batch::{Batch, BatchError}, Currency};
+    ///
+    /// let mixed = [Currency::from(('€', 100)), Currency::from(('$', 100))];
+    /// assert_eq!(Batch::from_currencies(&mixed).unwrap_err(), BatchError::CurrencyMismatch);
+    /// ```
+    pub fn from_currencies(currencies: &'a [Currency]) -> Result<Batch<'a>, BatchError> {
+        let symbol = currencies.first().and_then(|money| money.symbol);
+        if currencies.iter().any(|money| money.symbol != symbol) {
+            return Err(BatchError::CurrencyMismatch);
+        }
+        Ok(Batch {
+            symbol,
+            values: Values::Currencies(currencies),
+        })
+    }
+
+    /// The symbol shared by all amounts.
+    pub fn symbol(&self) -> Option<Symbol> {
+        self.symbol
+    }
+
+    /// The number of amounts.
+    pub fn len(&self) -> usize {
+        match self.values {
+            Values::Raw(values) => values.len(),
+            #[cfg(feature = "arrow")]
+            Values::Wide(values) => values.len(),
+            Values::Currencies(currencies) => currencies.len(),
+        }
+    }
+
+    /// Whether the batch holds no amounts.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn currency(&self, value: i64) -> Currency {
+        Currency {
+            symbol: self.symbol,
+            value,
+        }
+    }
+
+    /// Adds up all amounts, zero for an empty batch.
+    ///
+    /// Overflow is handled like the `+` operator, see [`math`](crate::math#overflow).
+    pub fn sum(&self) -> Currency {
+        if cfg!(feature = "overflow-checks") {
+            return self
+                .checked_sum()
+                .unwrap_or_else(|| panic!("Currency overflow: attempt to add with overflow"));
+        }
+        self.currency(with_values!(self, |values| values.sum::<i64>()))
+    }
+
+    /// Adds up all amounts, returning `None` if the total does not fit.
+    ///
+    /// Intermediate sums may exceed the range of `i64` as long as the total fits.
+    pub fn checked_sum(&self) -> Option<Currency> {
+        let total = with_values!(self, |values| values
+            .fold(0i128, |acc, value| acc + i128::from(value)));
+        i64::try_from(total).ok().map(|value| self.currency(value))
+    }
+
+    /// The smallest amount, `None` for an empty batch.
+    pub fn min(&self) -> Option<Currency> {
+        if self.is_empty() {
+            return None;
+        }
+        let min = with_values!(self, |values| values.fold(i64::MAX, i64::min));
+        Some(self.currency(min))
+    }
+
+    /// The largest amount, `None` for an empty batch.
+    pub fn max(&self) -> Option<Currency> {
+        if self.is_empty() {
+            return None;
+        }
+        let max = with_values!(self, |values| values.fold(i64::MIN, i64::max));
+        Some(self.currency(max))
+    }
+
+    /// Multiplies every amount by `rate`, rounding each product to the smallest unit with `mode`.
+    ///
+    /// Returns the values in the currency of the batch, or [`BatchError::Overflow`] if any product
+    /// does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{batch::Batch, decimal::Decimal, rounding::RoundingMode};
+    ///
+    /// let prices = [1999, 250, 5];
+    /// let batch = Batch::from_values(Some('$'), &prices);
+    /// let rate = Decimal::new(1075, 3);
+    /// assert_eq!(batch.scale(rate, RoundingMode::HalfUp), Ok(vec![2149, 269, 5]));
+    /// ```
+    pub fn scale(&self, rate: Decimal, mode: RoundingMode) -> Result<Vec<i64>, BatchError> {
+        let factor = rate.mantissa();
+        let divisor = pow10(rate.scale());
+        let mut scaled = Vec::with_capacity(self.len());
+        // most rates fit into an `i64` together with the products, so the 128 bit arithmetic is
+        // only needed for the rest
+        let narrow = i64::try_from(factor)
+            .ok()
+            .and_then(|factor| Some((factor, i64::try_from(divisor).ok()?)));
+        with_values!(self, |values| {
+            for value in values {
+                let narrow_product = narrow
+                    .and_then(|(factor, divisor)| Some((value.checked_mul(factor)?, divisor)));
+                let rounded = match narrow_product {
+                    Some((product, divisor)) => div_round_i64(product, divisor, mode),
+                    None => {
+                        let product = i128::from(value)
+                            .checked_mul(factor)
+                            .ok_or(BatchError::Overflow)?;
+                        i64::try_from(div_round(product, divisor, mode))
+                            .map_err(|_| BatchError::Overflow)?
+                    }
+                };
+                scaled.push(rounded);
+            }
+        });
+        Ok(scaled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn euros(value: i64) -> Currency {
+        Currency::from(('€', value))
+    }
+
+    #[test]
+    fn raw_and_currencies_agree() {
+        let values = [300, -1200, 45, 7, 0, 999];
+        let currencies: Vec<Currency> = values.iter().map(|&value| euros(value)).collect();
+        let raw = Batch::from_values(Some('€'), &values);
+        let checked = Batch::from_currencies(&currencies).unwrap();
+
+        assert_eq!(raw.sum(), euros(151));
+        assert_eq!(checked.sum(), raw.sum());
+        assert_eq!(checked.sum(), currencies.iter().sum::<Currency>());
+        assert_eq!(raw.min(), Some(euros(-1200)));
+        assert_eq!(checked.min(), raw.min());
+        assert_eq!(raw.max(), Some(euros(999)));
+        assert_eq!(checked.max(), raw.max());
+        assert_eq!(raw.len(), 6);
+        assert_eq!(checked.symbol(), Some('€'));
+    }
+
+    #[test]
+    fn empty() {
+        let batch = Batch::from_currencies(&[]).unwrap();
+        assert!(batch.is_empty());
+        assert_eq!(batch.sum(), Currency::default());
+        assert_eq!(batch.checked_sum(), Some(Currency::default()));
+        assert_eq!(batch.min(), None);
+        assert_eq!(batch.max(), None);
+        assert_eq!(
+            batch.scale(Decimal::new(2, 0), RoundingMode::Up),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn mismatch() {
+        assert_eq!(
+            Batch::from_currencies(&[euros(1), euros(2), Currency::from(3)]).unwrap_err(),
+            BatchError::CurrencyMismatch
+        );
+    }
+
+    #[test]
+    fn checked_sum() {
+        let values = [i64::MAX, 1, -2];
+        let batch = Batch::from_values(None, &values);
+        assert_eq!(batch.checked_sum(), Some(Currency::from(i64::MAX - 1)));
+
+        let values = [i64::MAX, 1];
+        assert_eq!(Batch::from_values(None, &values).checked_sum(), None);
+        let values = [i64::MIN, -1];
+        assert_eq!(Batch::from_values(None, &values).checked_sum(), None);
+    }
+
+    #[test]
+    fn scale() {
+        let values = [100, 5, -5, 15];
+        let batch = Batch::from_values(Some('€'), &values);
+        let half = Decimal::new(5, 1);
+        assert_eq!(
+            batch.scale(half, RoundingMode::HalfEven),
+            Ok(vec![50, 2, -2, 8])
+        );
+        assert_eq!(
+            batch.scale(half, RoundingMode::Down),
+            Ok(vec![50, 2, -2, 7])
+        );
+
+        let values = [i64::MAX];
+        let batch = Batch::from_values(None, &values);
+        assert_eq!(
+            batch.scale(Decimal::new(2, 0), RoundingMode::HalfUp),
+            Err(BatchError::Overflow)
+        );
+        assert_eq!(
+            batch.scale(Decimal::new(i128::MAX, 0), RoundingMode::HalfUp),
+            Err(BatchError::Overflow)
+        );
+        // the product fits into 128 bits, but 2.5 times the value does not fit into 64
+        assert_eq!(
+            batch.scale(Decimal::new(25, 1), RoundingMode::HalfUp),
+            Err(BatchError::Overflow)
+        );
+        // the product only fits into 128 bits, the result fits again
+        assert_eq!(
+            batch.scale(Decimal::new(25, 2), RoundingMode::HalfUp),
+            Ok(vec![i64::MAX / 4 + 1])
+        );
+    }
+}
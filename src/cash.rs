@@ -0,0 +1,135 @@
+//! Cash rounding to the smallest physical denomination.
+//!
+//! Where the smallest coins have been withdrawn, cash payments are rounded to a multiple of the
+//! smallest coin, e.g. `0.05` in Switzerland, Canada and Australia. The exact amount is kept for
+//! accounting, the difference is reported separately, see
+//! [`Invoice::cash_increment`](crate::invoice::Invoice::cash_increment).
+
+use crate::info::CurrencyInfo;
+use crate::rounding::{div_round, RoundingMode};
+use crate::Currency;
+
+/// Cash rounding.
+impl Currency {
+    /// Rounds to a multiple of `increment`, given in the smallest unit.
+    ///
+    /// Returns `None` if `increment` is not positive or the result does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{rounding::RoundingMode, Currency};
+    ///
+    /// let exact = Currency::from(1238);
+    /// assert_eq!(exact.round_to_increment(5, RoundingMode::HalfUp), Some(Currency::from(1240)));
+    /// assert_eq!(exact.round_to_increment(5, RoundingMode::Down), Some(Currency::from(1235)));
+    /// assert_eq!(exact.round_to_increment(0, RoundingMode::Down), None);
+    /// ```
+    pub fn round_to_increment(&self, increment: i64, mode: RoundingMode) -> Option<Currency> {
+        if increment <= 0 {
+            return None;
+        }
+        let increment = i128::from(increment);
+        let value = div_round(i128::from(self.value), increment, mode) * increment;
+        if value > i128::from(i64::MAX) || value < i128::from(i64::MIN) {
+            return None;
+        }
+        Some(Currency {
+            symbol: self.symbol,
+            value: value as i64,
+        })
+    }
+
+    /// Rounds to the [`cash_increment`](CurrencyInfo::cash_increment) of the currency, amounts
+    /// without metadata are returned unchanged.
+    ///
+    /// The currency is looked up by symbol, use [`Currency::round_to_cash_for`] for currencies
+    /// without a symbol such as CHF.
+    ///
+    /// Returns `None` if the result does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{rounding::RoundingMode, Currency};
+    ///
+    /// let yen = Currency::from(('¥', 12_350));
+    /// assert_eq!(yen.round_to_cash(RoundingMode::HalfEven), Some(Currency::from(('¥', 12_400))));
+    /// ```
+    pub fn round_to_cash(&self, mode: RoundingMode) -> Option<Currency> {
+        match self.info() {
+            Some(info) => self.round_to_cash_for(info, mode),
+            None => self.round_to_increment(1, mode),
+        }
+    }
+
+    /// Rounds to the [`cash_increment`](CurrencyInfo::cash_increment) of `info`, regardless of
+    /// the symbol.
+    ///
+    /// Returns `None` if the result does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{info::CurrencyInfo, rounding::RoundingMode, Currency};
+    ///
+    /// let chf = CurrencyInfo::from_code("CHF").unwrap();
+    /// let exact = Currency::from(1238);
+    /// assert_eq!(exact.round_to_cash_for(chf, RoundingMode::HalfUp), Some(Currency::from(1240)));
+    /// ```
+    pub fn round_to_cash_for(&self, info: &CurrencyInfo, mode: RoundingMode) -> Option<Currency> {
+        self.round_to_increment(info.cash_increment(), mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swiss_rounding() {
+        let increment = CurrencyInfo::from_code("CHF").unwrap().cash_increment();
+        let round = |value| {
+            Currency::from(value)
+                .round_to_increment(increment, RoundingMode::HalfUp)
+                .unwrap()
+                .value
+        };
+        assert_eq!(round(1), 0);
+        assert_eq!(round(2), 0);
+        assert_eq!(round(3), 5);
+        assert_eq!(round(7), 5);
+        assert_eq!(round(8), 10);
+        assert_eq!(round(-3), -5);
+        assert_eq!(round(-2), 0);
+    }
+
+    #[test]
+    fn cash_increment_by_code() {
+        let round = |code, value| {
+            let info = CurrencyInfo::from_code(code).unwrap();
+            Currency::from(value)
+                .round_to_cash_for(info, RoundingMode::HalfUp)
+                .unwrap()
+                .value
+        };
+        assert_eq!(round("CHF", 1238), 1240);
+        assert_eq!(round("CHF", -1237), -1235);
+        assert_eq!(round("CAD", 1997), 1995);
+        assert_eq!(round("CAD", 1998), 2000);
+        assert_eq!(round("USD", 1998), 1998);
+    }
+
+    #[test]
+    fn overflow() {
+        let max = Currency::from(i64::MAX);
+        assert_eq!(max.round_to_increment(10, RoundingMode::Up), None);
+        assert_eq!(
+            max.round_to_increment(10, RoundingMode::Down),
+            Some(Currency::from(i64::MAX - 7))
+        );
+    }
+
+    #[test]
+    fn unknown_currencies_are_unchanged() {
+        let money = Currency::from(('₿', 1234));
+        assert_eq!(money.round_to_cash(RoundingMode::Up), Some(money));
+    }
+}
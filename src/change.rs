@@ -0,0 +1,297 @@
+//! Notes and coins: breaking amounts into denominations and making change from a cash drawer.
+//!
+//! Results are lists of `(denomination, count)` pairs, largest denomination first, leaving out
+//! denominations that are not used.
+
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+
+use crate::Currency;
+
+/// Largest amount [`make_change`] accepts, in multiples of the greatest common divisor of the
+/// denominations.
+pub const MAX_CHANGE_UNITS: i64 = 1_000_000;
+
+/// Reasons why an amount cannot be paid out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeError {
+    /// There is no denomination table for the currency.
+    UnknownCurrency,
+    /// The amount or the inventory uses a different symbol.
+    CurrencyMismatch,
+    /// The amount is negative.
+    Negative,
+    /// A denomination in the inventory is not positive.
+    InvalidDenomination,
+    /// The amount exceeds [`MAX_CHANGE_UNITS`].
+    TooLarge,
+    /// The amount cannot be paid exactly with the available notes and coins.
+    Impossible,
+}
+
+impl fmt::Display for ChangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ChangeError::UnknownCurrency => "no denominations known for this currency",
+            ChangeError::CurrencyMismatch => "amount and inventory must share a currency",
+            ChangeError::Negative => "cannot pay out a negative amount",
+            ChangeError::InvalidDenomination => "denominations must be positive",
+            ChangeError::TooLarge => "amount too large to make change for",
+            ChangeError::Impossible => "exact change is impossible",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for ChangeError {}
+
+fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        let rest = a % b;
+        a = b;
+        b = rest;
+    }
+    a
+}
+
+/// A note or coin of the currency of `money`.
+fn piece(money: &Currency, value: i64) -> Currency {
+    Currency {
+        symbol: money.symbol,
+        value,
+    }
+}
+
+/// Denominations.
+impl Currency {
+    /// Breaks the amount into the fewest notes and coins in circulation, see
+    /// [`CurrencyInfo::denominations`](crate::info::CurrencyInfo::denominations).
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::Currency;
+    ///
+    /// let counts = Currency::from(('€', 4711)).breakdown().unwrap();
+    /// let counts: Vec<(i64, u64)> = counts.iter().map(|(money, count)| (money.value, *count)).collect();
+    /// assert_eq!(counts, vec![(2000, 2), (500, 1), (200, 1), (10, 1), (1, 1)]);
+    /// ```
+    pub fn breakdown(&self) -> Result<Vec<(Currency, u64)>, ChangeError> {
+        let info = self.info().ok_or(ChangeError::UnknownCurrency)?;
+        if self.value < 0 {
+            return Err(ChangeError::Negative);
+        }
+        let mut rest = self.value;
+        let mut counts = Vec::new();
+        for &denomination in info.denominations() {
+            let count = rest / denomination;
+            if count > 0 {
+                rest -= count * denomination;
+                counts.push((piece(self, denomination), count as u64));
+            }
+        }
+        if rest != 0 {
+            return Err(ChangeError::Impossible);
+        }
+        Ok(counts)
+    }
+}
+
+/// Pays out `amount` with the fewest pieces from a limited `inventory` of `(denomination, count)`
+/// pairs.
+///
+/// The denominations don't need to be in circulation or sorted. Unlike [`Currency::breakdown`]
+/// this finds the optimum even where taking the largest piece first fails, e.g. €0.06 from three
+/// 2 cent coins and a 5 cent coin.
+///
+/// # Examples
+/// ```
+/// use claude::{change::{make_change, ChangeError}, Currency};
+///
+/// let euros = |value| Currency::from(('€', value));
+/// let drawer = [(euros(500), 1), (euros(200), 4), (euros(50), 1)];
+///
+/// assert_eq!(
+///     make_change(&euros(850), &drawer),
+///     Ok(vec![(euros(200), 4), (euros(50), 1)])
+/// );
+/// assert_eq!(make_change(&euros(100), &drawer), Err(ChangeError::Impossible));
+/// ```
+pub fn make_change(
+    amount: &Currency,
+    inventory: &[(Currency, u64)],
+) -> Result<Vec<(Currency, u64)>, ChangeError> {
+    if amount.value < 0 {
+        return Err(ChangeError::Negative);
+    }
+    let mut denominations: Vec<(i64, u64)> = Vec::new();
+    for &(money, count) in inventory {
+        if money.symbol != amount.symbol {
+            return Err(ChangeError::CurrencyMismatch);
+        }
+        if money.value <= 0 {
+            return Err(ChangeError::InvalidDenomination);
+        }
+        match denominations
+            .iter_mut()
+            .find(|(value, _)| *value == money.value)
+        {
+            Some(entry) => entry.1 = entry.1.saturating_add(count),
+            None => denominations.push((money.value, count)),
+        }
+    }
+    denominations.sort_by_key(|&(value, _)| Reverse(value));
+
+    let unit = denominations
+        .iter()
+        .fold(0, |unit, &(value, _)| gcd(unit, value));
+    if amount.value == 0 {
+        return Ok(Vec::new());
+    }
+    if unit == 0 || amount.value % unit != 0 {
+        return Err(ChangeError::Impossible);
+    }
+    let target = amount.value / unit;
+    if target > MAX_CHANGE_UNITS {
+        return Err(ChangeError::TooLarge);
+    }
+    let target = target as usize;
+
+    // Bounded knapsack: every denomination is split into bundles of 1, 2, 4, … pieces, each bundle
+    // can be taken once.
+    let mut bundles: Vec<(usize, usize, u64)> = Vec::new();
+    for (index, &(value, count)) in denominations.iter().enumerate() {
+        let value = (value / unit) as usize;
+        let usable = count.min((target / value) as u64);
+        let mut size = 1;
+        let mut left = usable;
+        while left > 0 {
+            let take = size.min(left);
+            bundles.push((index, value * take as usize, take));
+            left -= take;
+            size *= 2;
+        }
+    }
+
+    let mut fewest = vec![u64::MAX; target + 1];
+    fewest[0] = 0;
+    // one bit per bundle and total, set if the bundle is part of the best way to pay the total
+    let mut taken = vec![vec![0u64; target / 64 + 1]; bundles.len()];
+    for (bundle, &(_, value, pieces)) in bundles.iter().enumerate() {
+        for total in (value..=target).rev() {
+            let before = fewest[total - value];
+            if before != u64::MAX && before + pieces < fewest[total] {
+                fewest[total] = before + pieces;
+                taken[bundle][total / 64] |= 1 << (total % 64);
+            }
+        }
+    }
+    if fewest[target] == u64::MAX {
+        return Err(ChangeError::Impossible);
+    }
+
+    let mut counts = vec![0; denominations.len()];
+    let mut rest = target;
+    for (bundle, &(index, value, pieces)) in bundles.iter().enumerate().rev() {
+        if taken[bundle][rest / 64] & 1 << (rest % 64) != 0 {
+            counts[index] += pieces;
+            rest -= value;
+        }
+    }
+    Ok(denominations
+        .iter()
+        .zip(counts)
+        .filter(|&(_, count)| count > 0)
+        .map(|(&(value, _), count)| (piece(amount, value), count))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn euros(value: i64) -> Currency {
+        Currency::from(('€', value))
+    }
+
+    fn counts(result: Result<Vec<(Currency, u64)>, ChangeError>) -> Vec<(i64, u64)> {
+        result
+            .unwrap()
+            .iter()
+            .map(|(money, count)| (money.value, *count))
+            .collect()
+    }
+
+    #[test]
+    fn breakdown() {
+        assert_eq!(
+            counts(euros(123_456).breakdown()),
+            vec![
+                (50_000, 2),
+                (20_000, 1),
+                (2000, 1),
+                (1000, 1),
+                (200, 2),
+                (50, 1),
+                (5, 1),
+                (1, 1)
+            ]
+        );
+        assert_eq!(counts(euros(0).breakdown()), vec![]);
+        assert_eq!(euros(-1).breakdown(), Err(ChangeError::Negative));
+        assert_eq!(
+            Currency::from(100).breakdown(),
+            Err(ChangeError::UnknownCurrency)
+        );
+        // there is no half yen coin
+        assert_eq!(
+            Currency::from(('¥', 150)).breakdown(),
+            Err(ChangeError::Impossible)
+        );
+    }
+
+    #[test]
+    fn greedy_would_fail() {
+        let drawer = [(euros(5), 1), (euros(2), 3)];
+        assert_eq!(counts(make_change(&euros(6), &drawer)), vec![(2, 3)]);
+
+        let drawer = [(euros(1), 10), (euros(3), 10), (euros(4), 10)];
+        assert_eq!(counts(make_change(&euros(6), &drawer)), vec![(3, 2)]);
+    }
+
+    #[test]
+    fn limited_inventory() {
+        let drawer = [(euros(100), 2), (euros(50), 10), (euros(100), 1)];
+        assert_eq!(
+            counts(make_change(&euros(450), &drawer)),
+            vec![(100, 3), (50, 3)]
+        );
+        assert_eq!(
+            make_change(&euros(1050), &drawer),
+            Err(ChangeError::Impossible)
+        );
+        assert_eq!(
+            make_change(&euros(25), &drawer),
+            Err(ChangeError::Impossible)
+        );
+        assert_eq!(make_change(&euros(0), &[]), Ok(vec![]));
+        assert_eq!(make_change(&euros(1), &[]), Err(ChangeError::Impossible));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let drawer = [(euros(100), 2)];
+        assert_eq!(
+            make_change(&Currency::from(100), &drawer),
+            Err(ChangeError::CurrencyMismatch)
+        );
+        assert_eq!(
+            make_change(&euros(100), &[(euros(0), 1)]),
+            Err(ChangeError::InvalidDenomination)
+        );
+        assert_eq!(
+            make_change(&euros(10_000_001), &[(euros(1), u64::MAX)]),
+            Err(ChangeError::TooLarge)
+        );
+    }
+}
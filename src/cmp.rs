@@ -0,0 +1,151 @@
+//! Comparison of currencies.
+//!
+//! Amounts of different currencies have no natural order, so `PartialOrd` only compares
+//! currencies with the same symbol. To sort a mixed list use [`by_code`] or [`ByCode`].
+
+use std::cmp::Ordering;
+
+use crate::Currency;
+
+/// Compares the values of two currencies with the same symbol.
+///
+/// Returns `None` if the symbols differ, so neither `$1000 < €1` nor `$1000 > €1` holds.
+///
+/// # Examples
+/// ```
+/// use claude::Currency;
+///
+/// let dollars = Currency::from(('$', 100000));
+/// let euros = Currency::from(('€', 100));
+///
+/// assert!(Currency::from(('$', 100)) < dollars);
+/// assert_eq!(dollars.partial_cmp(&euros), None);
+/// assert!(!(dollars < euros) && !(dollars > euros));
+/// ```
+impl PartialOrd for Currency {
+    fn partial_cmp(&self, other: &Currency) -> Option<Ordering> {
+        if self.symbol == other.symbol {
+            Some(self.value.cmp(&other.value))
+        } else {
+            None
+        }
+    }
+}
+
+/// Orders currencies by their ISO 4217 code, then by amount.
+///
+/// Currencies without known metadata are ordered by their symbol instead, currencies without a
+/// symbol come first.
+///
+/// # Examples
+/// ```
+/// use claude::{cmp::by_code, Currency};
+///
+/// let mut list = vec![
+///     Currency::from(('$', 5)),
+///     Currency::from(('€', 7)),
+///     Currency::from(('$', 3)),
+///     Currency::from(9),
+/// ];
+/// list.sort_by(by_code);
+/// assert_eq!(
+///     list,
+///     vec![
+///         Currency::from(9),
+///         Currency::from(('€', 7)),
+///         Currency::from(('$', 3)),
+///         Currency::from(('$', 5)),
+///     ]
+/// );
+/// ```
+pub fn by_code(a: &Currency, b: &Currency) -> Ordering {
+    let code = |money: &Currency| money.info().map(|info| info.code());
+    let symbol_key = |money: &Currency| {
+        code(money)
+            .map(String::from)
+            .or_else(|| money.symbol.map(String::from))
+    };
+    symbol_key(a)
+        .cmp(&symbol_key(b))
+        .then_with(|| a.symbol.cmp(&b.symbol))
+        .then_with(|| a.value.cmp(&b.value))
+}
+
+/// Wrapper implementing `Ord` according to [`by_code`], e.g. for `BTreeMap` keys or
+/// `sort`/`max` on mixed currencies.
+///
+/// # Examples
+/// ```
+/// use claude::{cmp::ByCode, Currency};
+///
+/// let mut list = vec![ByCode(Currency::from(('€', 7))), ByCode(Currency::from(('$', 3)))];
+/// list.sort();
+/// assert_eq!(list[0].0, Currency::from(('€', 7)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ByCode(pub Currency);
+
+impl PartialOrd for ByCode {
+    fn partial_cmp(&self, other: &ByCode) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByCode {
+    fn cmp(&self, other: &ByCode) -> Ordering {
+        by_code(&self.0, &other.0)
+    }
+}
+
+impl From<Currency> for ByCode {
+    fn from(money: Currency) -> ByCode {
+        ByCode(money)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashMap};
+
+    #[test]
+    fn different_symbols_do_not_compare() {
+        let dollars = Currency::from(('$', 100000));
+        let euros = Currency::from(('€', 1));
+        assert_eq!(dollars.partial_cmp(&euros), None);
+        assert_eq!(Currency::from(1).partial_cmp(&euros), None);
+    }
+
+    #[test]
+    fn by_code_groups_and_sorts() {
+        let set: BTreeSet<ByCode> = vec![
+            Currency::from(('£', 2)),
+            Currency::from(('₿', 1)),
+            Currency::from(('$', 2)),
+            Currency::from(('£', 1)),
+        ]
+        .into_iter()
+        .map(ByCode)
+        .collect();
+        let sorted: Vec<Currency> = set.into_iter().map(|c| c.0).collect();
+        assert_eq!(
+            sorted,
+            vec![
+                Currency::from(('£', 1)),
+                Currency::from(('£', 2)),
+                Currency::from(('$', 2)),
+                Currency::from(('₿', 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn hash_map_keys() {
+        let mut totals = HashMap::new();
+        *totals.entry(Currency::from(('$', 100))).or_insert(0) += 1;
+        *totals.entry(Currency::from(('$', 100))).or_insert(0) += 1;
+        *totals.entry(Currency::from(('€', 100))).or_insert(0) += 1;
+        assert_eq!(totals[&Currency::from(('$', 100))], 2);
+        assert_eq!(totals.len(), 2);
+    }
+}
@@ -0,0 +1,316 @@
+//! Compact storage for many amounts of one currency.
+//!
+//! A `Vec<Currency>` repeats the symbol next to every value and takes 16 bytes per amount. A
+//! [`CurrencyColumn`] keeps the symbol once and the values in a `Vec<i64>`, and hands out
+//! [`Currency`] values when read.
+//!
+//! # Examples
+//! ```
+//! use std::convert::TryFrom;
+//! use claude::{column::CurrencyColumn, Currency};
+//!
+//! let amounts = vec![Currency::from(('€', 1250)), Currency::from(('€', 300))];
+//! let mut column = CurrencyColumn::try_from(amounts).unwrap();
+//! column.push(Currency::from(('€', 99))).unwrap();
+//!
+//! assert_eq!(column.values(), &[1250, 300, 99]);
+//! assert_eq!(column.sum(), Currency::from(('€', 1649)));
+//! assert!(column.push(Currency::from(('$', 1))).is_err());
+//! ```
+
+use std::convert::TryFrom;
+use std::iter::FusedIterator;
+use std::slice;
+
+use crate::batch::{Batch, BatchError};
+use crate::decimal::Decimal;
+use crate::rounding::RoundingMode;
+use crate::{Currency, Symbol};
+
+/// Amounts of one currency, stored as a symbol and a `Vec<i64>`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct CurrencyColumn {
+    symbol: Option<Symbol>,
+    values: Vec<i64>,
+}
+
+impl CurrencyColumn {
+    /// Creates an empty column for `symbol`.
+    pub fn new(symbol: Option<Symbol>) -> CurrencyColumn {
+        CurrencyColumn::from_values(symbol, Vec::new())
+    }
+
+    /// Creates an empty column for `symbol` with room for `capacity` amounts.
+    pub fn with_capacity(symbol: Option<Symbol>, capacity: usize) -> CurrencyColumn {
+        CurrencyColumn::from_values(symbol, Vec::with_capacity(capacity))
+    }
+
+    /// Treats `values`, given in the smallest unit, as amounts of `symbol`.
+    pub fn from_values(symbol: Option<Symbol>, values: Vec<i64>) -> CurrencyColumn {
+        CurrencyColumn { symbol, values }
+    }
+
+    /// The symbol shared by all amounts.
+    pub fn symbol(&self) -> Option<Symbol> {
+        self.symbol
+    }
+
+    /// The plain values in the smallest unit.
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    /// Gives up the column, keeping the plain values.
+    pub fn into_values(self) -> Vec<i64> {
+        self.values
+    }
+
+    /// The number of amounts.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the column holds no amounts.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The amount at `index`.
+    pub fn get(&self, index: usize) -> Option<Currency> {
+        self.values.get(index).map(|&value| self.currency(value))
+    }
+
+    fn currency(&self, value: i64) -> Currency {
+        Currency {
+            symbol: self.symbol,
+            value,
+        }
+    }
+
+    /// Appends `money`, failing with [`BatchError::CurrencyMismatch`] if its symbol differs.
+    pub fn push(&mut self, money: Currency) -> Result<(), BatchError> {
+        if money.symbol != self.symbol {
+            return Err(BatchError::CurrencyMismatch);
+        }
+        self.values.push(money.value);
+        Ok(())
+    }
+
+    /// Appends a plain value in the smallest unit.
+    pub fn push_value(&mut self, value: i64) {
+        self.values.push(value);
+    }
+
+    /// Iterates over the amounts.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            symbol: self.symbol,
+            values: self.values.iter(),
+        }
+    }
+
+    /// Borrows the amounts for the operations of [`Batch`].
+    pub fn as_batch(&self) -> Batch<'_> {
+        Batch::from_values(self.symbol, &self.values)
+    }
+
+    /// Adds up all amounts, see [`Batch::sum`].
+    pub fn sum(&self) -> Currency {
+        self.as_batch().sum()
+    }
+
+    /// Adds up all amounts, returning `None` if the total does not fit.
+    pub fn checked_sum(&self) -> Option<Currency> {
+        self.as_batch().checked_sum()
+    }
+
+    /// The smallest amount, `None` for an empty column.
+    pub fn min(&self) -> Option<Currency> {
+        self.as_batch().min()
+    }
+
+    /// The largest amount, `None` for an empty column.
+    pub fn max(&self) -> Option<Currency> {
+        self.as_batch().max()
+    }
+
+    /// Multiplies every amount by `rate`, see [`Batch::scale`].
+    pub fn scale(&self, rate: Decimal, mode: RoundingMode) -> Result<CurrencyColumn, BatchError> {
+        let values = self.as_batch().scale(rate, mode)?;
+        Ok(CurrencyColumn::from_values(self.symbol, values))
+    }
+
+    /// A new column with the amounts for which `predicate` returns `true`.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{column::CurrencyColumn, Currency};
+    ///
+    /// let column = CurrencyColumn::from_values(Some('£'), vec![500, -120, 0, 75]);
+    /// let credits = column.filter(|money| money.value > 0);
+    /// assert_eq!(credits.values(), &[500, 75]);
+    /// ```
+    pub fn filter<F>(&self, mut predicate: F) -> CurrencyColumn
+    where
+        F: FnMut(&Currency) -> bool,
+    {
+        let values = self
+            .iter()
+            .filter(|money| predicate(money))
+            .map(|money| money.value)
+            .collect();
+        CurrencyColumn::from_values(self.symbol, values)
+    }
+
+    /// Keeps only the amounts for which `predicate` returns `true`.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&Currency) -> bool,
+    {
+        let symbol = self.symbol;
+        self.values
+            .retain(|&value| predicate(&Currency { symbol, value }));
+    }
+}
+
+impl TryFrom<Vec<Currency>> for CurrencyColumn {
+    type Error = BatchError;
+
+    /// Fails with [`BatchError::CurrencyMismatch`] if the amounts have different symbols. An empty
+    /// `Vec` makes an empty column without a symbol.
+    fn try_from(amounts: Vec<Currency>) -> Result<CurrencyColumn, BatchError> {
+        let batch = Batch::from_currencies(&amounts)?;
+        let values = amounts.iter().map(|money| money.value).collect();
+        Ok(CurrencyColumn::from_values(batch.symbol(), values))
+    }
+}
+
+impl From<CurrencyColumn> for Vec<Currency> {
+    fn from(column: CurrencyColumn) -> Vec<Currency> {
+        column.iter().collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a CurrencyColumn {
+    type Item = Currency;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the amounts of a [`CurrencyColumn`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    symbol: Option<Symbol>,
+    values: slice::Iter<'a, i64>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Currency;
+
+    fn next(&mut self) -> Option<Currency> {
+        let symbol = self.symbol;
+        self.values.next().map(|&value| Currency { symbol, value })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Currency> {
+        let symbol = self.symbol;
+        self.values
+            .next_back()
+            .map(|&value| Currency { symbol, value })
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dollars(value: i64) -> Currency {
+        Currency::from(('$', value))
+    }
+
+    #[test]
+    fn round_trip() {
+        let amounts = vec![dollars(100), dollars(-5), dollars(12_345)];
+        let column = CurrencyColumn::try_from(amounts.clone()).unwrap();
+        assert_eq!(column.symbol(), Some('$'));
+        assert_eq!(column.values(), &[100, -5, 12_345]);
+        assert_eq!(column.get(1), Some(dollars(-5)));
+        assert_eq!(column.get(3), None);
+        assert_eq!(column.iter().next_back(), Some(dollars(12_345)));
+        assert_eq!(column.iter().len(), 3);
+        assert_eq!(Vec::from(column), amounts);
+    }
+
+    #[test]
+    fn mixed_currencies() {
+        let amounts = vec![dollars(100), Currency::from(('€', 100))];
+        assert_eq!(
+            CurrencyColumn::try_from(amounts),
+            Err(BatchError::CurrencyMismatch)
+        );
+
+        let mut column = CurrencyColumn::new(Some('$'));
+        assert_eq!(
+            column.push(Currency::from(100)),
+            Err(BatchError::CurrencyMismatch)
+        );
+        assert!(column.is_empty());
+    }
+
+    #[test]
+    fn empty() {
+        let column = CurrencyColumn::try_from(Vec::new()).unwrap();
+        assert_eq!(column, CurrencyColumn::default());
+        assert_eq!(column.sum(), Currency::default());
+        assert_eq!(column.max(), None);
+        assert!(Vec::<Currency>::from(column).is_empty());
+    }
+
+    #[test]
+    fn aggregates() {
+        let mut column = CurrencyColumn::with_capacity(Some('$'), 4);
+        column.push(dollars(250)).unwrap();
+        column.push_value(-75);
+        column.push_value(1000);
+        assert_eq!(column.sum(), dollars(1175));
+        assert_eq!(column.checked_sum(), Some(dollars(1175)));
+        assert_eq!(column.min(), Some(dollars(-75)));
+        assert_eq!(column.max(), Some(dollars(1000)));
+        assert_eq!(
+            column
+                .scale(Decimal::new(1, 1), RoundingMode::HalfUp)
+                .unwrap()
+                .values(),
+            &[25, -8, 100]
+        );
+
+        column.retain(|money| money.value >= 0);
+        assert_eq!(column.into_values(), vec![250, 1000]);
+    }
+
+    #[cfg(feature = "serialization")]
+    #[test]
+    fn serialization() {
+        let column = CurrencyColumn::from_values(Some('€'), vec![4711, -1]);
+        let json = serde_json::to_string(&column).unwrap();
+        assert_eq!(json, r#"{"symbol":"€","values":[4711,-1]}"#);
+        assert_eq!(
+            serde_json::from_str::<CurrencyColumn>(&json).unwrap(),
+            column
+        );
+    }
+}
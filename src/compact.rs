@@ -0,0 +1,260 @@
+//! Compact display of large amounts for dashboards: `$4.5M`, `12,3 Tsd. €`, `₹1.2Cr`.
+
+use std::fmt;
+
+use crate::display::write_amount;
+use crate::locale::{Grouping, Language, Locale};
+use crate::rounding::{div_round, RoundingMode};
+use crate::Currency;
+
+/// Enough significant digits for every digit of any amount.
+const MAX_SIGNIFICANT_DIGITS: u32 = 19;
+
+const ENGLISH_UNITS: &[(u128, &str)] = &[
+    (1, ""),
+    (1_000, "K"),
+    (1_000_000, "M"),
+    (1_000_000_000, "B"),
+    (1_000_000_000_000, "T"),
+];
+
+const INDIAN_UNITS: &[(u128, &str)] = &[(1, ""), (1_000, "K"), (100_000, "L"), (10_000_000, "Cr")];
+
+const GERMAN_UNITS: &[(u128, &str)] = &[
+    (1, ""),
+    (1_000, " Tsd."),
+    (1_000_000, " Mio."),
+    (1_000_000_000, " Mrd."),
+    (1_000_000_000_000, " Bio."),
+];
+
+/// Implements `Display` by abbreviating the amount to a number of significant digits and a
+/// suffix such as `K` or `Mio.`.
+///
+/// The amount is rounded once, directly from the exact value. If rounding carries over into the
+/// next unit the next unit is used instead, so `999.95K` becomes `1.0M` rather than `1000.0K`.
+/// Trailing zeros are kept, the output always has the configured number of significant digits,
+/// but amounts below a thousand never show more than two fractional digits.
+pub struct Compact<'a> {
+    money: &'a Currency,
+    significant_digits: u32,
+    locale: Locale,
+    rounding: RoundingMode,
+}
+
+impl<'a> Compact<'a> {
+    pub(crate) fn new(money: &'a Currency) -> Compact<'a> {
+        Compact {
+            money,
+            significant_digits: 3,
+            locale: Locale::EnUs,
+            rounding: RoundingMode::HalfUp,
+        }
+    }
+
+    /// Number of significant digits to show, `3` by default. Values below `1` are treated as `1`,
+    /// values above `19` as `19`, which shows every digit of any amount.
+    pub fn significant_digits(mut self, digits: u32) -> Compact<'a> {
+        self.significant_digits = digits.clamp(1, MAX_SIGNIFICANT_DIGITS);
+        self
+    }
+
+    /// Locale deciding the suffixes, decimal separator and symbol position, `EnUs` by default.
+    pub fn locale(mut self, locale: Locale) -> Compact<'a> {
+        self.locale = locale;
+        self
+    }
+
+    /// How to round to the significant digits, `HalfUp` by default.
+    pub fn rounding(mut self, mode: RoundingMode) -> Compact<'a> {
+        self.rounding = mode;
+        self
+    }
+
+    fn units(&self) -> &'static [(u128, &'static str)] {
+        match (self.locale.language(), self.locale.grouping()) {
+            (_, Grouping::Indian) => INDIAN_UNITS,
+            (Language::German, _) => GERMAN_UNITS,
+            (Language::English, _) => ENGLISH_UNITS,
+        }
+    }
+
+    /// Rounds the absolute amount, returns the digits, the number of fractional digits and the
+    /// suffix.
+    fn abbreviate(&self) -> (u128, u32, &'static str) {
+        let cents = self.money.value.unsigned_abs() as u128;
+        let units = self.units();
+        let mut index = units
+            .iter()
+            .rposition(|&(unit, _)| cents >= unit * 100)
+            .unwrap_or(0);
+
+        loop {
+            let (unit, suffix) = units[index];
+            let unit_cents = unit * 100;
+            let integer_digits = decimal_digits(cents / unit_cents);
+            let max_fraction = if index == 0 { 2 } else { u32::MAX };
+            let mut fraction = self
+                .significant_digits
+                .saturating_sub(integer_digits)
+                .min(max_fraction);
+
+            let mut scaled = self.round(cents, unit_cents, fraction);
+            if fraction > 0 && decimal_digits(scaled) > self.significant_digits.max(fraction + 1) {
+                fraction -= 1;
+                scaled = self.round(cents, unit_cents, fraction);
+            }
+
+            match units.get(index + 1) {
+                Some(&(next, _)) if scaled >= next / unit * 10u128.pow(fraction) => index += 1,
+                _ => return (scaled, fraction, suffix),
+            }
+        }
+    }
+
+    fn round(&self, cents: u128, unit_cents: u128, fraction: u32) -> u128 {
+        let mode = match self.rounding {
+            RoundingMode::Ceiling if self.money.value < 0 => RoundingMode::Floor,
+            RoundingMode::Floor if self.money.value < 0 => RoundingMode::Ceiling,
+            mode => mode,
+        };
+        let numerator = (cents * 10u128.pow(fraction)) as i128;
+        div_round(numerator, unit_cents as i128, mode) as u128
+    }
+}
+
+fn decimal_digits(n: u128) -> u32 {
+    let mut digits = 1;
+    let mut rest = n / 10;
+    while rest > 0 {
+        digits += 1;
+        rest /= 10;
+    }
+    digits
+}
+
+/// Abbreviates the amount, honouring width, fill, alignment and the `+` flag.
+///
+/// # Examples
+/// ```
+/// use claude::{locale::Locale, Currency};
+///
+/// let revenue = Currency::from(('$', 450_000_000));
+/// assert_eq!(revenue.compact().to_string(), "$4.50M");
+/// assert_eq!(revenue.compact().significant_digits(2).to_string(), "$4.5M");
+///
+/// let costs = Currency::from(('€', 1_234_567));
+/// assert_eq!(costs.compact().locale(Locale::DeDe).to_string(), "12,3 Tsd. €");
+/// ```
+impl<'a> fmt::Display for Compact<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (scaled, fraction, suffix) = self.abbreviate();
+        let divisor = 10u128.pow(fraction);
+
+        let mut number = (scaled / divisor).to_string();
+        if fraction > 0 {
+            number.push(self.locale.decimal_separator());
+            number.push_str(&format!(
+                "{:0width$}",
+                scaled % divisor,
+                width = fraction as usize
+            ));
+        }
+        number.push_str(suffix);
+
+        let negative = self.money.value < 0 && scaled > 0;
+        match (self.money.symbol, self.locale.language()) {
+            (None, _) => write_amount(f, negative, "", &number, ""),
+            (Some(symbol), Language::English) => {
+                write_amount(f, negative, &symbol.to_string(), &number, "")
+            }
+            (Some(symbol), Language::German) => {
+                write_amount(f, negative, "", &number, &format!(" {}", symbol))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compact(value: i64, digits: u32, locale: Locale) -> String {
+        Currency::from(('$', value))
+            .compact()
+            .significant_digits(digits)
+            .locale(locale)
+            .to_string()
+    }
+
+    #[test]
+    fn suffixes() {
+        assert_eq!(compact(1_230_000, 3, Locale::EnUs), "$12.3K");
+        assert_eq!(compact(123_000_000_000, 3, Locale::EnUs), "$1.23B");
+        assert_eq!(compact(500_000_000_000_000, 2, Locale::EnUs), "$5.0T");
+        assert_eq!(compact(12_345, 3, Locale::EnUs), "$123");
+        assert_eq!(compact(512, 3, Locale::EnUs), "$5.12");
+        assert_eq!(compact(5, 3, Locale::EnUs), "$0.05");
+        assert_eq!(compact(-1_230_000, 3, Locale::EnUs), "-$12.3K");
+    }
+
+    #[test]
+    fn locales() {
+        assert_eq!(compact(250_000_000, 2, Locale::DeDe), "2,5 Mio. $");
+        assert_eq!(compact(730_000_000_000, 2, Locale::DeDe), "7,3 Mrd. $");
+        assert_eq!(compact(12_000_000, 2, Locale::EnIn), "$1.2L");
+        assert_eq!(compact(3_450_000_000, 3, Locale::EnIn), "$3.45Cr");
+        assert_eq!(compact(1_234_567_000_000, 3, Locale::EnIn), "$1235Cr");
+    }
+
+    #[test]
+    fn rounding_near_boundaries() {
+        assert_eq!(compact(99_995_000, 4, Locale::EnUs), "$1.000M");
+        assert_eq!(compact(99_995_000, 2, Locale::EnUs), "$1.0M");
+        assert_eq!(compact(99_940_000, 4, Locale::EnUs), "$999.4K");
+        assert_eq!(compact(999_500, 3, Locale::EnUs), "$10.0K");
+        assert_eq!(compact(99_950, 3, Locale::EnUs), "$1.00K");
+        assert_eq!(compact(9_995_000, 3, Locale::EnUs), "$100K");
+        assert_eq!(compact(9_999_999, 3, Locale::EnIn), "$1.00L");
+    }
+
+    #[test]
+    fn many_significant_digits() {
+        assert_eq!(
+            compact(123_456_789, 50, Locale::EnUs),
+            compact(123_456_789, 19, Locale::EnUs)
+        );
+        assert_eq!(
+            compact(123_456_789, 50, Locale::EnUs),
+            "$1.234567890000000000M"
+        );
+        assert_eq!(
+            compact(i64::MAX, u32::MAX, Locale::EnUs),
+            "$92233.72036854775807T"
+        );
+        assert_eq!(compact(-5, 50, Locale::EnIn), "-$0.05");
+    }
+
+    #[test]
+    fn formatter_flags() {
+        let money = Currency::from(('$', 1_230_000));
+        assert_eq!(
+            format!("{:>8}|{:+}", money.compact(), money.compact()),
+            "  $12.3K|+$12.3K"
+        );
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let floor = |value| {
+            Currency::from(('$', value))
+                .compact()
+                .significant_digits(2)
+                .rounding(RoundingMode::Floor)
+                .to_string()
+        };
+        assert_eq!(floor(99_999_999), "$999K");
+        assert_eq!(floor(-1_010_000), "-$11K");
+        assert_eq!(floor(i64::MIN), "-$92234T");
+    }
+}
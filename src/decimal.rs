@@ -0,0 +1,290 @@
+//! Exact decimal numbers, used for ratios, rates and quantities.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::rounding::{div_round, RoundingMode};
+
+/// A decimal number `mantissa × 10^-scale`, e.g. `Decimal::new(1759, 3)` is `1.759`.
+///
+/// Decimals compare by value, `1.50 == 1.5`.
+///
+/// # Examples
+/// ```
+/// use claude::decimal::Decimal;
+/// use claude::rounding::RoundingMode;
+///
+/// let rate: Decimal = "0.0375".parse().unwrap();
+/// assert_eq!(rate, Decimal::new(375, 4));
+/// assert_eq!(rate.rescale(2, RoundingMode::HalfUp), Some(Decimal::new(4, 2)));
+/// assert_eq!(rate.to_string(), "0.0375");
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+/// The largest supported scale, i.e. the number of fractional digits.
+pub const MAX_SCALE: u32 = 28;
+
+pub(crate) fn pow10(exponent: u32) -> i128 {
+    10i128.pow(exponent)
+}
+
+impl Decimal {
+    /// Creates `mantissa × 10^-scale`.
+    ///
+    /// # Panics
+    /// Panics if `scale` is larger than [`MAX_SCALE`].
+    pub fn new(mantissa: i128, scale: u32) -> Decimal {
+        assert!(
+            scale <= MAX_SCALE,
+            "scale {} exceeds the maximum of {}",
+            scale,
+            MAX_SCALE
+        );
+        Decimal { mantissa, scale }
+    }
+
+    /// The digits of the number without the decimal point.
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// The number of fractional digits.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// Whether the number is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    /// Changes the number of fractional digits, rounding with `mode` if digits are dropped.
+    ///
+    /// Returns `None` if `scale` is larger than [`MAX_SCALE`] or the result does not fit.
+    pub fn rescale(&self, scale: u32, mode: RoundingMode) -> Option<Decimal> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        let mantissa = if scale >= self.scale {
+            self.mantissa.checked_mul(pow10(scale - self.scale))?
+        } else {
+            div_round(self.mantissa, pow10(self.scale - scale), mode)
+        };
+        Some(Decimal { mantissa, scale })
+    }
+
+    /// Removes trailing fractional zeros, `1.500` becomes `1.5`.
+    pub fn normalized(&self) -> Decimal {
+        let mut normalized = *self;
+        while normalized.scale > 0 && normalized.mantissa % 10 == 0 {
+            normalized.mantissa /= 10;
+            normalized.scale -= 1;
+        }
+        normalized
+    }
+
+    /// Multiplies two decimals exactly, returning `None` if the result does not fit.
+    ///
+    /// The scale of the product is the sum of both scales, trailing zeros are removed if it would
+    /// exceed [`MAX_SCALE`].
+    pub fn checked_mul(&self, other: &Decimal) -> Option<Decimal> {
+        let mantissa = self.mantissa.checked_mul(other.mantissa)?;
+        let product = Decimal {
+            mantissa,
+            scale: self.scale + other.scale,
+        };
+        if product.scale <= MAX_SCALE {
+            return Some(product);
+        }
+        let normalized = product.normalized();
+        if normalized.scale <= MAX_SCALE {
+            Some(normalized)
+        } else {
+            None
+        }
+    }
+
+    /// Adds two decimals exactly, returning `None` if the result does not fit.
+    pub fn checked_add(&self, other: &Decimal) -> Option<Decimal> {
+        let scale = self.scale.max(other.scale);
+        let lhs = self.mantissa.checked_mul(pow10(scale - self.scale))?;
+        let rhs = other.mantissa.checked_mul(pow10(scale - other.scale))?;
+        Some(Decimal {
+            mantissa: lhs.checked_add(rhs)?,
+            scale,
+        })
+    }
+
+    /// Splits into the integer part and the fractional remainder, both with the sign of `self`.
+    fn split(&self) -> (i128, i128) {
+        let unit = pow10(self.scale);
+        (self.mantissa / unit, self.mantissa % unit)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Decimal {
+        Decimal {
+            mantissa: i128::from(value),
+            scale: 0,
+        }
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        let (lhs_int, lhs_frac) = self.split();
+        let (rhs_int, rhs_frac) = other.split();
+        let scale = self.scale.max(other.scale);
+        lhs_int.cmp(&rhs_int).then_with(|| {
+            let lhs = lhs_frac * pow10(scale - self.scale);
+            let rhs = rhs_frac * pow10(scale - other.scale);
+            lhs.cmp(&rhs)
+        })
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalized();
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (int, frac) = self.split();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        write!(f, "{}{}", sign, int.unsigned_abs())?;
+        if self.scale > 0 {
+            write!(
+                f,
+                ".{:0width$}",
+                frac.unsigned_abs(),
+                width = self.scale as usize
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a string is not a plain decimal number like `-12.345`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDecimalError(());
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not a valid decimal number")
+    }
+}
+
+impl Error for ParseDecimalError {}
+
+/// Parses plain decimal numbers: an optional `-`, digits and an optional `.` followed by digits.
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    fn from_str(s: &str) -> Result<Decimal, ParseDecimalError> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (int, frac) = match digits.find('.') {
+            Some(pos) => (&digits[..pos], &digits[pos + 1..]),
+            None => (digits, ""),
+        };
+        let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty()
+            || !all_digits(int)
+            || !all_digits(frac)
+            || (digits.contains('.') && frac.is_empty())
+            || frac.len() > MAX_SCALE as usize
+        {
+            return Err(ParseDecimalError(()));
+        }
+
+        let mut mantissa: i128 = 0;
+        for digit in int.bytes().chain(frac.bytes()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(i128::from(digit - b'0')))
+                .ok_or(ParseDecimalError(()))?;
+        }
+        Ok(Decimal {
+            mantissa: if negative { -mantissa } else { mantissa },
+            scale: frac.len() as u32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(dec("12.345"), Decimal::new(12345, 3));
+        assert_eq!(dec("-0.05").to_string(), "-0.05");
+        assert_eq!(dec("7").to_string(), "7");
+        assert_eq!(Decimal::new(-5, 1).to_string(), "-0.5");
+        for invalid in &["", "-", ".5", "5.", "1.2.3", "1,5", "+1", "1e5", "--1"] {
+            assert!(invalid.parse::<Decimal>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn compares_by_value() {
+        assert_eq!(dec("1.50"), dec("1.5"));
+        assert!(dec("-0.5") < dec("0.3"));
+        assert!(dec("-1.5") < dec("-1.25"));
+        assert!(dec("2") > dec("1.999999"));
+    }
+
+    #[test]
+    fn rescale() {
+        let mode = RoundingMode::HalfEven;
+        assert_eq!(dec("2.345").rescale(2, mode), Some(dec("2.34")));
+        assert_eq!(dec("2.5").rescale(0, mode), Some(dec("2")));
+        assert_eq!(dec("2.5").rescale(4, mode).unwrap().mantissa(), 25000);
+        assert_eq!(dec("1").rescale(MAX_SCALE + 1, mode), None);
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(dec("1.5").checked_mul(&dec("0.2")), Some(dec("0.3")));
+        assert_eq!(dec("1.5").checked_add(&dec("-0.25")), Some(dec("1.25")));
+        assert_eq!(dec("0.5").normalized().scale(), 1);
+        assert_eq!(dec("0.500").normalized(), Decimal::new(5, 1));
+    }
+}
@@ -1,9 +1,17 @@
 //! `Display` Implementation.
+//!
+//! All adapters honour the flags of the `Formatter`:
+//!
+//! * width, fill and alignment, right aligned by default like other numbers
+//! * `+` to print a sign for positive amounts
+//! * `0` to pad with zeros between the sign or prefixed symbol and the digits
+//! * precision to override the number of fractional digits, rounding half away from zero
 
-use std::fmt;
+use std::fmt::{self, Write};
 use std::ops::Deref;
 
 use super::{Currency, Postfix, Prefix};
+use crate::rounding::{div_round, RoundingMode};
 
 macro_rules! impl_deref_to_currency {
     ($s:ty) => {
@@ -20,6 +28,83 @@ macro_rules! impl_deref_to_currency {
 impl_deref_to_currency!(Postfix<'a>);
 impl_deref_to_currency!(Prefix<'a>);
 
+/// Formats the absolute value of `cents` with `precision` fractional digits.
+///
+/// Returns the digits and whether the rounded amount is zero.
+pub(crate) fn decimal(cents: i64, precision: usize, separator: char) -> (String, bool) {
+    let abs = i128::from(cents.unsigned_abs());
+    let (int, frac) = if precision >= 2 {
+        let frac = format!("{:02}{:0<pad$}", abs % 100, "", pad = precision - 2);
+        (abs / 100, frac)
+    } else {
+        let rounded = div_round(abs, 10i128.pow(2 - precision as u32), RoundingMode::HalfUp);
+        let unit = 10i128.pow(precision as u32);
+        let frac = if precision == 1 {
+            (rounded % unit).to_string()
+        } else {
+            String::new()
+        };
+        (rounded / unit, frac)
+    };
+
+    let is_zero = int == 0 && frac.bytes().all(|b| b == b'0');
+    let mut digits = int.to_string();
+    if !frac.is_empty() {
+        digits.push(separator);
+        digits.push_str(&frac);
+    }
+    (digits, is_zero)
+}
+
+/// Writes an amount made up of a sign, a prefix, the digits and a suffix, honouring width, fill,
+/// alignment, the `+` flag and zero padding.
+pub(crate) fn write_amount(
+    f: &mut fmt::Formatter<'_>,
+    negative: bool,
+    prefix: &str,
+    digits: &str,
+    suffix: &str,
+) -> fmt::Result {
+    let sign = match (negative, f.sign_plus()) {
+        (true, _) => "-",
+        (false, true) => "+",
+        (false, false) => "",
+    };
+    let len = [sign, prefix, digits, suffix]
+        .iter()
+        .map(|part| part.chars().count())
+        .sum::<usize>();
+    let padding = f.width().unwrap_or(0).saturating_sub(len);
+
+    if f.sign_aware_zero_pad() {
+        f.write_str(sign)?;
+        f.write_str(prefix)?;
+        for _ in 0..padding {
+            f.write_char('0')?;
+        }
+        f.write_str(digits)?;
+        return f.write_str(suffix);
+    }
+
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Right) | None => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(sign)?;
+    f.write_str(prefix)?;
+    f.write_str(digits)?;
+    f.write_str(suffix)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
 /// Allows Currencies to be displayed as Strings.
 /// The format includes no comma delimiting with a two digit precision decimal.
 ///
@@ -28,6 +113,7 @@ impl_deref_to_currency!(Prefix<'a>);
 /// use claude::Currency;
 ///
 /// assert!(Currency{ symbol: None, value: 1210}.postfix().to_string() == "12,10");
+/// assert_eq!(format!("{:>9}", Currency{ symbol: Some('€'), value: -1210}.postfix()), "  -12,10€");
 ///
 /// println!("{}", Currency{ symbol: Some('€'), value: 100099}.postfix());
 /// ```
@@ -38,11 +124,13 @@ impl_deref_to_currency!(Prefix<'a>);
 impl<'a> fmt::Display for Postfix<'a> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let decimal = format!("{:.2}", (self.value as f32 / 100.0)).replace('.', ",");
-        match self.symbol {
-            Some(symbol) => write!(f, "{d}{s}", s = symbol, d = decimal),
-            None => write!(f, "{}", decimal),
-        }
+        let (digits, is_zero) = decimal(self.value, f.precision().unwrap_or(2), ',');
+        let mut symbol = [0; 4];
+        let suffix = match self.symbol {
+            Some(symbol_char) => &*symbol_char.encode_utf8(&mut symbol),
+            None => "",
+        };
+        write_amount(f, self.value < 0 && !is_zero, "", &digits, suffix)
     }
 }
 
@@ -55,6 +143,8 @@ impl<'a> fmt::Display for Postfix<'a> {
 ///
 /// assert!(Currency{ symbol: Some('$'), value: 1210}.prefix().to_string() == "$12.10");
 /// assert!(Currency{ symbol: None, value: 1210}.prefix().to_string() == "12.10");
+/// assert_eq!(format!("{:+.0}", Currency{ symbol: Some('$'), value: 1250}.prefix()), "+$13");
+/// assert_eq!(format!("{:*<8}", Currency{ symbol: Some('$'), value: -1210}.prefix()), "-$12.10*");
 ///
 /// println!("{}", Currency{ symbol: Some('$'), value: 100099}.prefix());
 /// ```
@@ -65,10 +155,83 @@ impl<'a> fmt::Display for Postfix<'a> {
 impl<'a> fmt::Display for Prefix<'a> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let decimal = format!("{:.2}", (self.value as f32 / 100.0));
-        match self.symbol {
-            Some(symbol) => write!(f, "{s}{d}", s = symbol, d = decimal),
-            None => write!(f, "{}", decimal),
+        let (digits, is_zero) = decimal(self.value, f.precision().unwrap_or(2), '.');
+        let mut symbol = [0; 4];
+        let prefix = match self.symbol {
+            Some(symbol_char) => &*symbol_char.encode_utf8(&mut symbol),
+            None => "",
+        };
+        write_amount(f, self.value < 0 && !is_zero, prefix, &digits, "")
+    }
+}
+
+/// Displays the currency like [`Currency::prefix`].
+///
+/// # Examples
+/// ```
+/// use claude::Currency;
+///
+/// let money = Currency{ symbol: Some('$'), value: 4711};
+/// assert_eq!(money.to_string(), "$47.11");
+/// assert_eq!(format!("{:>10.1}|{:010}", money, money), "     $47.1|$000047.11");
+/// ```
+impl fmt::Display for Currency {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.prefix(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Currency;
+
+    fn dollars(value: i64) -> Currency {
+        Currency {
+            symbol: Some('$'),
+            value,
         }
     }
+
+    #[test]
+    fn negative_sign_comes_first() {
+        assert_eq!(dollars(-1210).prefix().to_string(), "-$12.10");
+        assert_eq!(dollars(-5).postfix().to_string(), "-0,05$");
+    }
+
+    #[test]
+    fn alignment() {
+        assert_eq!(format!("{:>9}", dollars(1210).prefix()), "   $12.10");
+        assert_eq!(format!("{:<9}|", dollars(1210).prefix()), "$12.10   |");
+        assert_eq!(format!("{:^9}", dollars(1210).prefix()), " $12.10  ");
+        assert_eq!(format!("{:9}", dollars(1210).postfix()), "   12,10$");
+        assert_eq!(format!("{:_>9}", dollars(1210)), "___$12.10");
+        assert_eq!(format!("{:2}", dollars(1210)), "$12.10");
+    }
+
+    #[test]
+    fn sign() {
+        assert_eq!(format!("{:+}", dollars(1210)), "+$12.10");
+        assert_eq!(format!("{:+}", dollars(-1210)), "-$12.10");
+        assert_eq!(format!("{:+}", dollars(0).postfix()), "+0,00$");
+        assert_eq!(format!("{:+08}", dollars(-1210).postfix()), "-012,10$");
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(format!("{:.0}", dollars(1249)), "$12");
+        assert_eq!(format!("{:.0}", dollars(1250)), "$13");
+        assert_eq!(format!("{:.0}", dollars(-1250)), "-$13");
+        assert_eq!(format!("{:.1}", dollars(1249)), "$12.5");
+        assert_eq!(format!("{:.4}", dollars(1249)), "$12.4900");
+        assert_eq!(format!("{:.0}", dollars(-40)), "$0");
+        assert_eq!(format!("{:.1}", dollars(99)), "$1.0");
+    }
+
+    #[test]
+    fn large_values_are_exact() {
+        assert_eq!(dollars(i64::MAX).to_string(), "$92233720368547758.07");
+        assert_eq!(dollars(i64::MIN).to_string(), "-$92233720368547758.08");
+        assert_eq!(dollars(100_000_099).postfix().to_string(), "1000000,99$");
+    }
 }
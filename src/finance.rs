@@ -0,0 +1,348 @@
+//! Interest and time value of money.
+//!
+//! Rates are given per period as a [`Decimal`], e.g. `0.005` for 0.5% per month. Every function
+//! returns whole `Currency` amounts, rounded once at the end with the given [`RoundingMode`].
+//!
+//! Simple interest and the interest portions of an amortization schedule are computed exactly.
+//! Compounding factors `(1 + rate)^periods` are computed in fixed point with
+//! [`FACTOR_SCALE`] fractional digits, rates with more digits are rounded half to even first.
+//!
+//! # Examples
+//! ```
+//! use claude::{decimal::Decimal, finance, rounding::RoundingMode, Currency};
+//!
+//! let loan = Currency::from(('€', 1_000_000));
+//! let monthly = Decimal::new(5, 3); // 0.5% per month
+//!
+//! let payment = finance::payment(loan, monthly, 12, RoundingMode::HalfUp).unwrap();
+//! assert_eq!(payment, Currency::from(('€', 86066)));
+//!
+//! let schedule = finance::amortization_schedule(loan, monthly, 12, RoundingMode::HalfUp).unwrap();
+//! let repaid: i64 = schedule.iter().map(|row| row.principal.value()).sum();
+//! assert_eq!(repaid, loan.value());
+//! assert_eq!(schedule.last().unwrap().balance, Currency::from(('€', 0)));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::decimal::{pow10, Decimal};
+use crate::rounding::{div_round, RoundingMode};
+use crate::Currency;
+
+/// Number of fractional digits of the fixed point compounding factors.
+pub const FACTOR_SCALE: u32 = 12;
+
+/// Reasons why a calculation failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FinanceError {
+    /// The rate is `-100%` or less.
+    InvalidRate,
+    /// The calculation needs at least one period.
+    NoPeriods,
+    /// An intermediate result or the result does not fit.
+    Overflow,
+}
+
+impl fmt::Display for FinanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            FinanceError::InvalidRate => "rate must be greater than -100%",
+            FinanceError::NoPeriods => "at least one period is required",
+            FinanceError::Overflow => "amount out of range",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for FinanceError {}
+
+/// One period of an amortization schedule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AmortizationRow {
+    /// Number of the period, starting at `1`
+    pub period: u32,
+    /// Amount paid in this period, `principal + interest`
+    pub payment: Currency,
+    /// Part of the payment repaying the principal
+    pub principal: Currency,
+    /// Part of the payment covering the interest of this period
+    pub interest: Currency,
+    /// Remaining principal after this period
+    pub balance: Currency,
+}
+
+fn to_currency(money: Currency, value: i128) -> Result<Currency, FinanceError> {
+    if value > i128::from(i64::MAX) || value < i128::from(i64::MIN) {
+        return Err(FinanceError::Overflow);
+    }
+    Ok(Currency {
+        symbol: money.symbol,
+        value: value as i64,
+    })
+}
+
+/// `amount × rate`, exact and then rounded.
+fn apply_rate(amount: i128, rate: Decimal, mode: RoundingMode) -> Result<i128, FinanceError> {
+    let product = amount
+        .checked_mul(rate.mantissa())
+        .ok_or(FinanceError::Overflow)?;
+    Ok(div_round(product, pow10(rate.scale()), mode))
+}
+
+/// The rate in fixed point with [`FACTOR_SCALE`] digits.
+fn fixed_rate(rate: Decimal) -> Result<i128, FinanceError> {
+    let fixed = rate
+        .rescale(FACTOR_SCALE, RoundingMode::HalfEven)
+        .ok_or(FinanceError::Overflow)?
+        .mantissa();
+    if fixed <= -pow10(FACTOR_SCALE) {
+        return Err(FinanceError::InvalidRate);
+    }
+    Ok(fixed)
+}
+
+/// `(1 + rate)^periods` in fixed point with [`FACTOR_SCALE`] digits.
+fn growth_factor(rate: Decimal, periods: u32) -> Result<i128, FinanceError> {
+    let one = pow10(FACTOR_SCALE);
+    let base = one + fixed_rate(rate)?;
+    let mut factor = one;
+    for _ in 0..periods {
+        let product = factor.checked_mul(base).ok_or(FinanceError::Overflow)?;
+        factor = div_round(product, one, RoundingMode::HalfEven);
+    }
+    Ok(factor)
+}
+
+/// Interest without compounding: `principal × rate × periods`.
+pub fn simple_interest(
+    principal: Currency,
+    rate: Decimal,
+    periods: u32,
+    mode: RoundingMode,
+) -> Result<Currency, FinanceError> {
+    let amount = i128::from(principal.value) * i128::from(periods);
+    to_currency(principal, apply_rate(amount, rate, mode)?)
+}
+
+/// Value of `present` after `periods` periods of compound interest: `present × (1 + rate)^periods`.
+pub fn future_value(
+    present: Currency,
+    rate: Decimal,
+    periods: u32,
+    mode: RoundingMode,
+) -> Result<Currency, FinanceError> {
+    let factor = growth_factor(rate, periods)?;
+    let product = i128::from(present.value)
+        .checked_mul(factor)
+        .ok_or(FinanceError::Overflow)?;
+    to_currency(present, div_round(product, pow10(FACTOR_SCALE), mode))
+}
+
+/// Interest earned by compounding, `future_value - principal`.
+///
+/// The future value is rounded, the difference is exact.
+pub fn compound_interest(
+    principal: Currency,
+    rate: Decimal,
+    periods: u32,
+    mode: RoundingMode,
+) -> Result<Currency, FinanceError> {
+    let future = future_value(principal, rate, periods, mode)?;
+    to_currency(
+        principal,
+        i128::from(future.value) - i128::from(principal.value),
+    )
+}
+
+/// Amount needed today to reach `future` after `periods` periods:
+/// `future / (1 + rate)^periods`.
+pub fn present_value(
+    future: Currency,
+    rate: Decimal,
+    periods: u32,
+    mode: RoundingMode,
+) -> Result<Currency, FinanceError> {
+    let factor = growth_factor(rate, periods)?;
+    if factor == 0 {
+        return Err(FinanceError::Overflow);
+    }
+    let numerator = i128::from(future.value)
+        .checked_mul(pow10(FACTOR_SCALE))
+        .ok_or(FinanceError::Overflow)?;
+    to_currency(future, div_round(numerator, factor, mode))
+}
+
+/// Constant payment per period repaying `principal` with interest in `periods` periods (PMT):
+/// `principal × rate / (1 - (1 + rate)^-periods)`.
+pub fn payment(
+    principal: Currency,
+    rate: Decimal,
+    periods: u32,
+    mode: RoundingMode,
+) -> Result<Currency, FinanceError> {
+    if periods == 0 {
+        return Err(FinanceError::NoPeriods);
+    }
+    let fixed = fixed_rate(rate)?;
+    let principal_value = i128::from(principal.value);
+    if fixed == 0 {
+        return to_currency(
+            principal,
+            div_round(principal_value, i128::from(periods), mode),
+        );
+    }
+
+    let factor = growth_factor(rate, periods)?;
+    let denominator = (factor - pow10(FACTOR_SCALE))
+        .checked_mul(pow10(FACTOR_SCALE))
+        .ok_or(FinanceError::Overflow)?;
+    if denominator == 0 {
+        return Err(FinanceError::Overflow);
+    }
+    let numerator = principal_value
+        .checked_mul(fixed)
+        .and_then(|n| n.checked_mul(factor))
+        .ok_or(FinanceError::Overflow)?;
+    to_currency(principal, div_round(numerator, denominator, mode))
+}
+
+/// Schedule of constant payments repaying `principal` in `periods` periods.
+///
+/// The payment is calculated with [`payment`], the interest of every period is rounded with
+/// `mode`. The final row absorbs the rounding residue, its payment repays the remaining balance,
+/// so the principal column sums up exactly to `principal` and the final balance is zero.
+pub fn amortization_schedule(
+    principal: Currency,
+    rate: Decimal,
+    periods: u32,
+    mode: RoundingMode,
+) -> Result<Vec<AmortizationRow>, FinanceError> {
+    let installment = i128::from(payment(principal, rate, periods, mode)?.value);
+    let mut balance = i128::from(principal.value);
+    let mut rows = Vec::with_capacity(periods as usize);
+
+    for period in 1..=periods {
+        let interest = apply_rate(balance, rate, mode)?;
+        let repaid = if period == periods {
+            balance
+        } else {
+            installment - interest
+        };
+        balance -= repaid;
+        rows.push(AmortizationRow {
+            period,
+            payment: to_currency(principal, repaid + interest)?,
+            principal: to_currency(principal, repaid)?,
+            interest: to_currency(principal, interest)?,
+            balance: to_currency(principal, balance)?,
+        });
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn euros(value: i64) -> Currency {
+        Currency::from(('€', value))
+    }
+
+    fn rate(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    const MODE: RoundingMode = RoundingMode::HalfUp;
+
+    #[test]
+    fn simple() {
+        assert_eq!(
+            simple_interest(euros(100_000), rate("0.05"), 3, MODE),
+            Ok(euros(15_000))
+        );
+        assert_eq!(
+            simple_interest(euros(333), rate("0.015"), 1, MODE),
+            Ok(euros(5))
+        );
+        assert_eq!(
+            simple_interest(euros(333), rate("0.015"), 1, RoundingMode::Down),
+            Ok(euros(4))
+        );
+    }
+
+    #[test]
+    fn compound() {
+        // 1000 × 1.05^10 = 1628.894626777...
+        assert_eq!(
+            future_value(euros(100_000), rate("0.05"), 10, MODE),
+            Ok(euros(162_889))
+        );
+        assert_eq!(
+            compound_interest(euros(100_000), rate("0.05"), 10, MODE),
+            Ok(euros(62_889))
+        );
+        // 1628.89 / 1.05^10 = 999.999...
+        assert_eq!(
+            present_value(euros(162_889), rate("0.05"), 10, MODE),
+            Ok(euros(100_000))
+        );
+        assert_eq!(
+            future_value(euros(100_000), rate("0"), 10, MODE),
+            Ok(euros(100_000))
+        );
+    }
+
+    #[test]
+    fn pmt() {
+        // 200000 at 0.5% per month over 30 years = 1199.101050...
+        assert_eq!(
+            payment(euros(20_000_000), rate("0.005"), 360, MODE),
+            Ok(euros(119_910))
+        );
+        assert_eq!(payment(euros(1000), rate("0"), 3, MODE), Ok(euros(333)));
+        assert_eq!(
+            payment(euros(1000), rate("0"), 0, MODE),
+            Err(FinanceError::NoPeriods)
+        );
+        assert_eq!(
+            payment(euros(1000), rate("-1"), 3, MODE),
+            Err(FinanceError::InvalidRate)
+        );
+    }
+
+    #[test]
+    fn schedule_sums_to_principal() {
+        let principal = euros(20_000_000);
+        let schedule = amortization_schedule(principal, rate("0.005"), 360, MODE).unwrap();
+        assert_eq!(schedule.len(), 360);
+
+        let first = schedule[0];
+        assert_eq!(first.interest, euros(100_000));
+        assert_eq!(first.principal, euros(19_910));
+        assert_eq!(first.balance, euros(19_980_090));
+
+        let repaid: i64 = schedule.iter().map(|row| row.principal.value).sum();
+        assert_eq!(repaid, principal.value);
+        for row in &schedule {
+            assert_eq!(row.payment, row.principal + row.interest);
+        }
+        assert_eq!(schedule[358].payment, euros(119_910));
+        assert_eq!(schedule[359].balance, euros(0));
+    }
+
+    #[test]
+    fn zero_rate_schedule() {
+        let schedule = amortization_schedule(euros(1000), rate("0"), 3, MODE).unwrap();
+        let payments: Vec<i64> = schedule.iter().map(|row| row.payment.value).collect();
+        assert_eq!(payments, vec![333, 333, 334]);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(
+            future_value(euros(i64::MAX), rate("1"), 64, MODE),
+            Err(FinanceError::Overflow)
+        );
+    }
+}
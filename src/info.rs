@@ -0,0 +1,245 @@
+//! Metadata about well known currencies.
+//!
+//! A `Currency` only carries its symbol, this module maps symbols to ISO 4217 codes, the names
+//! of their units, the smallest amount payable in cash and the notes and coins in circulation.
+//!
+//! Currencies without a symbol of their own, such as `CHF`, can only be looked up by code.
+
+use crate::locale::Language;
+use crate::Symbol;
+
+/// Singular and plural form of the name of a unit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnitName {
+    /// Name of one unit, e.g. `"penny"`
+    pub singular: &'static str,
+    /// Name of several units, e.g. `"pence"`
+    pub plural: &'static str,
+}
+
+impl UnitName {
+    const fn new(singular: &'static str, plural: &'static str) -> UnitName {
+        UnitName { singular, plural }
+    }
+
+    /// Picks the grammatical number fitting `count`.
+    pub fn for_count(&self, count: u64) -> &'static str {
+        if count == 1 {
+            self.singular
+        } else {
+            self.plural
+        }
+    }
+}
+
+/// Names of the major unit (banknote) and the minor unit (coin) in one language.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnitNames {
+    /// Name of the major unit, e.g. `"dollar"`
+    pub major: UnitName,
+    /// Name of the minor unit, e.g. `"cent"`
+    pub minor: UnitName,
+}
+
+impl UnitNames {
+    const fn new(major: UnitName, minor: UnitName) -> UnitNames {
+        UnitNames { major, minor }
+    }
+}
+
+/// Metadata about a currency.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurrencyInfo {
+    code: &'static str,
+    symbol: Option<Symbol>,
+    english: UnitNames,
+    german: UnitNames,
+    cash_increment: i64,
+    denominations: &'static [i64],
+}
+
+const CURRENCIES: &[CurrencyInfo] = &[
+    CurrencyInfo {
+        code: "USD",
+        symbol: Some('$'),
+        english: UnitNames::new(
+            UnitName::new("dollar", "dollars"),
+            UnitName::new("cent", "cents"),
+        ),
+        german: UnitNames::new(
+            UnitName::new("Dollar", "Dollar"),
+            UnitName::new("Cent", "Cent"),
+        ),
+        cash_increment: 1,
+        denominations: &[10_000, 5000, 2000, 1000, 500, 200, 100, 50, 25, 10, 5, 1],
+    },
+    CurrencyInfo {
+        code: "EUR",
+        symbol: Some('€'),
+        english: UnitNames::new(
+            UnitName::new("euro", "euros"),
+            UnitName::new("cent", "cents"),
+        ),
+        german: UnitNames::new(UnitName::new("Euro", "Euro"), UnitName::new("Cent", "Cent")),
+        cash_increment: 1,
+        denominations: &[
+            50_000, 20_000, 10_000, 5000, 2000, 1000, 500, 200, 100, 50, 20, 10, 5, 2, 1,
+        ],
+    },
+    CurrencyInfo {
+        code: "GBP",
+        symbol: Some('£'),
+        english: UnitNames::new(
+            UnitName::new("pound", "pounds"),
+            UnitName::new("penny", "pence"),
+        ),
+        german: UnitNames::new(
+            UnitName::new("Pfund", "Pfund"),
+            UnitName::new("Penny", "Pence"),
+        ),
+        cash_increment: 1,
+        denominations: &[5000, 2000, 1000, 500, 200, 100, 50, 20, 10, 5, 2, 1],
+    },
+    CurrencyInfo {
+        code: "JPY",
+        symbol: Some('¥'),
+        english: UnitNames::new(UnitName::new("yen", "yen"), UnitName::new("sen", "sen")),
+        german: UnitNames::new(UnitName::new("Yen", "Yen"), UnitName::new("Sen", "Sen")),
+        cash_increment: 100,
+        denominations: &[
+            1_000_000, 500_000, 200_000, 100_000, 50_000, 10_000, 5000, 1000, 500, 100,
+        ],
+    },
+    CurrencyInfo {
+        code: "INR",
+        symbol: Some('₹'),
+        english: UnitNames::new(
+            UnitName::new("rupee", "rupees"),
+            UnitName::new("paisa", "paise"),
+        ),
+        german: UnitNames::new(
+            UnitName::new("Rupie", "Rupien"),
+            UnitName::new("Paisa", "Paise"),
+        ),
+        cash_increment: 100,
+        denominations: &[50_000, 20_000, 10_000, 5000, 2000, 1000, 500, 200, 100],
+    },
+    CurrencyInfo {
+        code: "CHF",
+        symbol: None,
+        english: UnitNames::new(
+            UnitName::new("franc", "francs"),
+            UnitName::new("centime", "centimes"),
+        ),
+        german: UnitNames::new(
+            UnitName::new("Franken", "Franken"),
+            UnitName::new("Rappen", "Rappen"),
+        ),
+        cash_increment: 5,
+        denominations: &[
+            100_000, 20_000, 10_000, 5000, 2000, 1000, 500, 200, 100, 50, 20, 10, 5,
+        ],
+    },
+    CurrencyInfo {
+        code: "CAD",
+        symbol: None,
+        english: UnitNames::new(
+            UnitName::new("dollar", "dollars"),
+            UnitName::new("cent", "cents"),
+        ),
+        german: UnitNames::new(
+            UnitName::new("Dollar", "Dollar"),
+            UnitName::new("Cent", "Cent"),
+        ),
+        cash_increment: 5,
+        denominations: &[10_000, 5000, 2000, 1000, 500, 200, 100, 25, 10, 5],
+    },
+    CurrencyInfo {
+        code: "AUD",
+        symbol: None,
+        english: UnitNames::new(
+            UnitName::new("dollar", "dollars"),
+            UnitName::new("cent", "cents"),
+        ),
+        german: UnitNames::new(
+            UnitName::new("Dollar", "Dollar"),
+            UnitName::new("Cent", "Cent"),
+        ),
+        cash_increment: 5,
+        denominations: &[10_000, 5000, 2000, 1000, 500, 200, 100, 50, 20, 10, 5],
+    },
+];
+
+impl CurrencyInfo {
+    /// Looks up the currency commonly denoted by `symbol`.
+    ///
+    /// Symbols shared by several currencies resolve to the most common one, `$` is `USD` and `¥`
+    /// is `JPY`.
+    pub fn from_symbol(symbol: Symbol) -> Option<&'static CurrencyInfo> {
+        CURRENCIES.iter().find(|info| info.symbol == Some(symbol))
+    }
+
+    /// Looks up a currency by its ISO 4217 code.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::info::CurrencyInfo;
+    ///
+    /// assert_eq!(CurrencyInfo::from_code("EUR").unwrap().symbol(), Some('€'));
+    /// assert!(CurrencyInfo::from_code("XYZ").is_none());
+    /// ```
+    pub fn from_code(code: &str) -> Option<&'static CurrencyInfo> {
+        CURRENCIES
+            .iter()
+            .find(|info| info.code.eq_ignore_ascii_case(code))
+    }
+
+    /// All known currencies.
+    pub fn all() -> &'static [CurrencyInfo] {
+        CURRENCIES
+    }
+
+    /// ISO 4217 code, e.g. `"EUR"`
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Symbol used for this currency, if it has a single character symbol.
+    pub fn symbol(&self) -> Option<Symbol> {
+        self.symbol
+    }
+
+    /// Smallest amount payable in cash, in the smallest unit, e.g. `5` for the 5 Rappen coin.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::info::CurrencyInfo;
+    ///
+    /// assert_eq!(CurrencyInfo::from_code("CHF").unwrap().cash_increment(), 5);
+    /// assert_eq!(CurrencyInfo::from_code("USD").unwrap().cash_increment(), 1);
+    /// ```
+    pub fn cash_increment(&self) -> i64 {
+        self.cash_increment
+    }
+
+    /// Values of the notes and coins in circulation in the smallest unit, largest first.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::info::CurrencyInfo;
+    ///
+    /// let gbp = CurrencyInfo::from_code("GBP").unwrap();
+    /// assert_eq!(gbp.denominations()[..3], [5000, 2000, 1000]);
+    /// ```
+    pub fn denominations(&self) -> &'static [i64] {
+        self.denominations
+    }
+
+    /// Names of the units in the given language.
+    pub fn unit_names(&self, language: Language) -> &UnitNames {
+        match language {
+            Language::English => &self.english,
+            Language::German => &self.german,
+        }
+    }
+}
@@ -0,0 +1,799 @@
+//! Invoices and receipts: line items, discounts, taxes and totals.
+//!
+//! Every line is `quantity × unit price`, reduced by the line discount and then by the invoice
+//! discount. Lines are grouped by tax rate, a line without its own rate uses the invoice rate.
+//!
+//! Prices are either net and taxes are added ([`TaxMode::Exclusive`]), or prices already include
+//! the tax and it is extracted ([`TaxMode::Inclusive`]). Amounts are rounded with the
+//! [`RoundingMode`] of the invoice, either for every line ([`RoundingStrategy::PerLine`]) or once
+//! per tax rate on the exact sums ([`RoundingStrategy::PerTotal`]).
+//!
+//! With a [cash increment](Invoice::cash_increment) the total stays exact and the amount payable
+//! in cash is reported next to it, together with the rounding difference.
+//!
+//! # Examples
+//! ```
+//! use claude::invoice::{Discount, Invoice, InvoiceLine};
+//! use claude::Currency;
+//!
+//! let dollars = |value| Currency::from(('$', value));
+//! let invoice = Invoice::new()
+//!     .tax_rate("19".parse().unwrap())
+//!     .line(InvoiceLine::new("Widget", 3, dollars(1999)))
+//!     .line(
+//!         InvoiceLine::new("Gadget", 1, dollars(500))
+//!             .discount(Discount::Percent("10".parse().unwrap()))
+//!             .tax_rate("7".parse().unwrap()),
+//!     );
+//!
+//! let totals = invoice.totals().unwrap();
+//! assert_eq!(totals.subtotal, dollars(6447));
+//! assert_eq!(totals.tax, dollars(1171));
+//! assert_eq!(totals.total, dollars(7618));
+//!
+//! println!("{}", invoice.render().unwrap());
+//! ```
+//! prints
+//! ```text
+//! Description  Qty  Unit price  Discount  Tax  Amount
+//! ---------------------------------------------------
+//! Widget         3      $19.99            19%  $59.97
+//! Gadget         1       $5.00       10%   7%   $4.50
+//! ---------------------------------------------------
+//!                                    Discount  -$0.50
+//!                                    Subtotal  $64.47
+//!                                      Tax 7%   $0.32
+//!                                     Tax 19%  $11.39
+//!                                       Total  $76.18
+//! ```
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use crate::decimal::{pow10, Decimal};
+use crate::rounding::{div_round, RoundingMode};
+use crate::{Currency, Symbol};
+
+/// Reasons why an invoice cannot be calculated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvoiceError {
+    /// Lines or discounts use different symbols.
+    CurrencyMismatch,
+    /// A tax rate is negative.
+    InvalidTaxRate,
+    /// The cash increment is not positive.
+    InvalidCashIncrement,
+    /// An amount does not fit.
+    Overflow,
+}
+
+impl fmt::Display for InvoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            InvoiceError::CurrencyMismatch => "invoice lines must share a currency",
+            InvoiceError::InvalidTaxRate => "tax rates must not be negative",
+            InvoiceError::InvalidCashIncrement => "cash increment must be positive",
+            InvoiceError::Overflow => "amount out of range",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for InvoiceError {}
+
+/// Whether prices include the tax.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TaxMode {
+    /// Prices are net, the tax is added on top.
+    Exclusive,
+    /// Prices are gross, the tax is contained in them.
+    Inclusive,
+}
+
+/// When amounts are rounded to the smallest unit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingStrategy {
+    /// Every line and its tax are rounded, totals are sums of rounded amounts.
+    PerLine,
+    /// Lines are summed exactly, the sum and the tax of every tax rate are rounded once.
+    PerTotal,
+}
+
+/// A reduction of a price.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Discount {
+    /// A percentage off, `10` means 10%.
+    Percent(Decimal),
+    /// A fixed amount off the line, not per unit.
+    Amount(Currency),
+}
+
+/// A line of an invoice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvoiceLine {
+    /// What was sold
+    pub description: String,
+    /// Number of units
+    pub quantity: u32,
+    /// Price of a single unit, net or gross depending on the [`TaxMode`]
+    pub unit_price: Currency,
+    /// Discount of this line
+    pub discount: Option<Discount>,
+    /// Tax rate in percent, overriding the invoice rate
+    pub tax_rate: Option<Decimal>,
+}
+
+impl InvoiceLine {
+    /// Creates a line of `quantity` units at `unit_price`.
+    pub fn new(description: &str, quantity: u32, unit_price: Currency) -> InvoiceLine {
+        InvoiceLine {
+            description: description.to_owned(),
+            quantity,
+            unit_price,
+            discount: None,
+            tax_rate: None,
+        }
+    }
+
+    /// Sets the discount of this line.
+    pub fn discount(mut self, discount: Discount) -> InvoiceLine {
+        self.discount = Some(discount);
+        self
+    }
+
+    /// Sets the tax rate of this line in percent.
+    pub fn tax_rate(mut self, percent: Decimal) -> InvoiceLine {
+        self.tax_rate = Some(percent);
+        self
+    }
+}
+
+/// Net amount and tax of one tax rate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TaxLine {
+    /// Tax rate in percent
+    pub rate: Decimal,
+    /// Sum of the net amounts taxed at this rate
+    pub net: Currency,
+    /// Tax at this rate
+    pub tax: Currency,
+}
+
+/// Totals of an invoice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Totals {
+    /// Sum of all discounts, in terms of the prices, i.e. net or gross depending on the
+    /// [`TaxMode`]
+    pub discount: Currency,
+    /// Sum of the net amounts
+    pub subtotal: Currency,
+    /// Net amount and tax per tax rate, by ascending rate
+    pub taxes: Vec<TaxLine>,
+    /// Sum of the taxes
+    pub tax: Currency,
+    /// Amount to pay, `subtotal + tax`
+    pub total: Currency,
+    /// Difference between `payable` and `total`, zero without a cash increment
+    pub cash_rounding: Currency,
+    /// Amount to pay in cash, `total` rounded to the cash increment
+    pub payable: Currency,
+}
+
+/// Intermediate results shared by [`Invoice::totals`] and [`Invoice::render`].
+struct Calculation {
+    symbol: Option<Symbol>,
+    undiscounted: i128,
+    /// Sum of the discounted prices, rounded like `subtotal`
+    discounted: i128,
+    lines: Vec<i128>,
+    groups: Vec<(Decimal, i128, i128)>,
+}
+
+fn negate(value: Decimal) -> Decimal {
+    Decimal::new(-value.mantissa(), value.scale())
+}
+
+fn percent_off(amount: Decimal, percent: Decimal) -> Result<Decimal, InvoiceError> {
+    amount
+        .checked_mul(&percent)
+        .and_then(|off| off.checked_mul(&Decimal::new(1, 2)))
+        .and_then(|off| amount.checked_add(&negate(off)))
+        .ok_or(InvoiceError::Overflow)
+}
+
+/// Limits a discounted price to zero if the discount took a non-negative price below it.
+fn clamp_discount(price: Decimal, discounted: Decimal) -> Decimal {
+    if !price.is_negative() && discounted.is_negative() {
+        Decimal::default()
+    } else {
+        discounted
+    }
+}
+
+fn to_currency(symbol: Option<Symbol>, value: i128) -> Result<Currency, InvoiceError> {
+    if value > i128::from(i64::MAX) || value < i128::from(i64::MIN) {
+        return Err(InvoiceError::Overflow);
+    }
+    Ok(Currency {
+        symbol,
+        value: value as i64,
+    })
+}
+
+/// Formats `-money` without negating it, which would overflow for `i64::MIN`.
+fn negated(money: Currency) -> String {
+    let formatted = money.to_string();
+    match formatted.strip_prefix('-') {
+        Some(magnitude) => magnitude.to_owned(),
+        None if money.value == 0 => formatted,
+        None => format!("-{}", formatted),
+    }
+}
+
+fn percent_label(percent: Decimal) -> String {
+    format!("{}%", percent.normalized())
+}
+
+/// An invoice or receipt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invoice {
+    lines: Vec<InvoiceLine>,
+    tax_rate: Decimal,
+    discount: Option<Decimal>,
+    tax_mode: TaxMode,
+    strategy: RoundingStrategy,
+    rounding: RoundingMode,
+    cash_increment: Option<i64>,
+}
+
+impl Default for Invoice {
+    fn default() -> Invoice {
+        Invoice::new()
+    }
+}
+
+impl Invoice {
+    /// Creates an empty invoice with exclusive taxes at 0%, rounded `HalfUp` per line.
+    pub fn new() -> Invoice {
+        Invoice {
+            lines: Vec::new(),
+            tax_rate: Decimal::default(),
+            discount: None,
+            tax_mode: TaxMode::Exclusive,
+            strategy: RoundingStrategy::PerLine,
+            rounding: RoundingMode::HalfUp,
+            cash_increment: None,
+        }
+    }
+
+    /// Adds a line.
+    pub fn line(mut self, line: InvoiceLine) -> Invoice {
+        self.lines.push(line);
+        self
+    }
+
+    /// Tax rate in percent for lines without their own rate, `0` by default.
+    pub fn tax_rate(mut self, percent: Decimal) -> Invoice {
+        self.tax_rate = percent;
+        self
+    }
+
+    /// A percentage taken off every line after its own discount.
+    pub fn discount(mut self, percent: Decimal) -> Invoice {
+        self.discount = Some(percent);
+        self
+    }
+
+    /// Whether prices include the tax, `Exclusive` by default.
+    pub fn tax_mode(mut self, mode: TaxMode) -> Invoice {
+        self.tax_mode = mode;
+        self
+    }
+
+    /// When amounts are rounded, `PerLine` by default.
+    pub fn strategy(mut self, strategy: RoundingStrategy) -> Invoice {
+        self.strategy = strategy;
+        self
+    }
+
+    /// How amounts are rounded, `HalfUp` by default.
+    pub fn rounding(mut self, mode: RoundingMode) -> Invoice {
+        self.rounding = mode;
+        self
+    }
+
+    /// Rounds the amount payable in cash to a multiple of `increment`, given in the smallest unit.
+    ///
+    /// The total is kept exact, the rounding difference is reported separately.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::info::CurrencyInfo;
+    /// use claude::invoice::{Invoice, InvoiceLine};
+    /// use claude::Currency;
+    ///
+    /// let chf = CurrencyInfo::from_code("CHF").unwrap();
+    /// let invoice = Invoice::new()
+    ///     .cash_increment(chf.cash_increment())
+    ///     .line(InvoiceLine::new("Gipfeli", 3, Currency::from(179)));
+    ///
+    /// let totals = invoice.totals().unwrap();
+    /// assert_eq!(totals.total, Currency::from(537));
+    /// assert_eq!(totals.cash_rounding, Currency::from(-2));
+    /// assert_eq!(totals.payable, Currency::from(535));
+    /// ```
+    pub fn cash_increment(mut self, increment: i64) -> Invoice {
+        self.cash_increment = Some(increment);
+        self
+    }
+
+    /// The lines of the invoice.
+    pub fn lines(&self) -> &[InvoiceLine] {
+        &self.lines
+    }
+
+    /// Calculates discount, subtotal, taxes and total.
+    pub fn totals(&self) -> Result<Totals, InvoiceError> {
+        self.calculation()?
+            .totals(self.cash_increment, self.rounding)
+    }
+
+    /// Renders the invoice as a plain text table followed by the totals.
+    pub fn render(&self) -> Result<String, InvoiceError> {
+        let calculation = self.calculation()?;
+        let totals = calculation.totals(self.cash_increment, self.rounding)?;
+        let symbol = calculation.symbol;
+
+        let mut rows = vec![[
+            "Description".to_owned(),
+            "Qty".to_owned(),
+            "Unit price".to_owned(),
+            "Discount".to_owned(),
+            "Tax".to_owned(),
+            "Amount".to_owned(),
+        ]];
+        for (line, &amount) in self.lines.iter().zip(&calculation.lines) {
+            let discount = match line.discount {
+                Some(Discount::Percent(percent)) => percent_label(percent),
+                Some(Discount::Amount(money)) => negated(money),
+                None => String::new(),
+            };
+            rows.push([
+                line.description.clone(),
+                line.quantity.to_string(),
+                line.unit_price.to_string(),
+                discount,
+                percent_label(line.tax_rate.unwrap_or(self.tax_rate)),
+                to_currency(symbol, amount)?.to_string(),
+            ]);
+        }
+
+        let mut footer = Vec::new();
+        if totals.discount.value != 0 {
+            footer.push(("Discount".to_owned(), negated(totals.discount)));
+        }
+        footer.push(("Subtotal".to_owned(), totals.subtotal.to_string()));
+        for tax in &totals.taxes {
+            footer.push((
+                format!("Tax {}", percent_label(tax.rate)),
+                tax.tax.to_string(),
+            ));
+        }
+        footer.push(("Total".to_owned(), totals.total.to_string()));
+        if totals.cash_rounding.value != 0 {
+            footer.push(("Cash rounding".to_owned(), totals.cash_rounding.to_string()));
+            footer.push(("Payable in cash".to_owned(), totals.payable.to_string()));
+        }
+
+        let mut widths = [0; 6];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        for (_, amount) in &footer {
+            widths[5] = widths[5].max(amount.chars().count());
+        }
+        let label_width = widths[..5].iter().sum::<usize>() + 2 * 4;
+        let rule = "-".repeat(label_width + 2 + widths[5]);
+
+        let mut out = String::new();
+        for (index, row) in rows.iter().enumerate() {
+            let _ = write!(out, "{:<width$}", row[0], width = widths[0]);
+            for (cell, &width) in row.iter().zip(&widths).skip(1) {
+                let _ = write!(out, "  {:>width$}", cell, width = width);
+            }
+            out.push('\n');
+            if index == 0 {
+                out.push_str(&rule);
+                out.push('\n');
+            }
+        }
+        out.push_str(&rule);
+        for (label, amount) in &footer {
+            let _ = write!(
+                out,
+                "\n{:>label_width$}  {:>amount_width$}",
+                label,
+                amount,
+                label_width = label_width,
+                amount_width = widths[5]
+            );
+        }
+        Ok(out)
+    }
+
+    fn calculation(&self) -> Result<Calculation, InvoiceError> {
+        let symbol = self.lines.first().and_then(|line| line.unit_price.symbol);
+        let mismatch = self.lines.iter().any(|line| {
+            line.unit_price.symbol != symbol
+                || matches!(line.discount, Some(Discount::Amount(money)) if money.symbol != symbol)
+        });
+        if mismatch {
+            return Err(InvoiceError::CurrencyMismatch);
+        }
+
+        let mut undiscounted: i128 = 0;
+        let mut lines = Vec::with_capacity(self.lines.len());
+        let mut groups: BTreeMap<Decimal, (Decimal, i128, i128)> = BTreeMap::new();
+        for line in &self.lines {
+            let rate = line.tax_rate.unwrap_or(self.tax_rate);
+            if rate.is_negative() {
+                return Err(InvoiceError::InvalidTaxRate);
+            }
+            let price = i128::from(line.unit_price.value) * i128::from(line.quantity);
+            undiscounted += price;
+
+            let exact = self.discounted(line, price)?;
+            let rounded = self.round(exact)?;
+            lines.push(rounded);
+
+            let group = groups.entry(rate).or_insert((Decimal::default(), 0, 0));
+            match self.strategy {
+                RoundingStrategy::PerLine => {
+                    let (net, tax) = self.split(Decimal::new(rounded, 0), rate)?;
+                    group.1 += net;
+                    group.2 += tax;
+                }
+                RoundingStrategy::PerTotal => {
+                    group.0 = group.0.checked_add(&exact).ok_or(InvoiceError::Overflow)?;
+                }
+            }
+        }
+
+        let mut discounted: i128 = match self.strategy {
+            RoundingStrategy::PerLine => lines.iter().sum(),
+            RoundingStrategy::PerTotal => 0,
+        };
+        let mut totals = Vec::with_capacity(groups.len());
+        for (rate, (exact, net, tax)) in groups {
+            let (net, tax) = match self.strategy {
+                RoundingStrategy::PerLine => (net, tax),
+                RoundingStrategy::PerTotal => {
+                    // the discount must reconcile with the sums that are rounded once
+                    discounted += self.round(exact)?;
+                    self.split(exact, rate)?
+                }
+            };
+            totals.push((rate, net, tax));
+        }
+        Ok(Calculation {
+            symbol,
+            undiscounted,
+            discounted,
+            lines,
+            groups: totals,
+        })
+    }
+
+    /// The exact price of a line after the line and invoice discounts.
+    ///
+    /// A discount never takes a price below zero, negative prices of refunds are kept.
+    fn discounted(&self, line: &InvoiceLine, price: i128) -> Result<Decimal, InvoiceError> {
+        let undiscounted = Decimal::new(price, 0);
+        let mut amount = match line.discount {
+            Some(Discount::Percent(percent)) => {
+                clamp_discount(undiscounted, percent_off(undiscounted, percent)?)
+            }
+            Some(Discount::Amount(money)) => clamp_discount(
+                undiscounted,
+                Decimal::new(price - i128::from(money.value), 0),
+            ),
+            None => undiscounted,
+        };
+        if let Some(percent) = self.discount {
+            amount = clamp_discount(amount, percent_off(amount, percent)?);
+        }
+        Ok(amount)
+    }
+
+    fn round(&self, amount: Decimal) -> Result<i128, InvoiceError> {
+        amount
+            .rescale(0, self.rounding)
+            .map(|rounded| rounded.mantissa())
+            .ok_or(InvoiceError::Overflow)
+    }
+
+    /// Splits an exact price into the rounded net amount and tax.
+    fn split(&self, price: Decimal, rate: Decimal) -> Result<(i128, i128), InvoiceError> {
+        match self.tax_mode {
+            TaxMode::Exclusive => {
+                let tax = price
+                    .checked_mul(&rate)
+                    .and_then(|tax| tax.checked_mul(&Decimal::new(1, 2)))
+                    .ok_or(InvoiceError::Overflow)?;
+                Ok((self.round(price)?, self.round(tax)?))
+            }
+            TaxMode::Inclusive => {
+                // tax = gross × rate / (100 + rate)
+                let gross = self.round(price)?;
+                let numerator = price.mantissa().checked_mul(rate.mantissa());
+                let denominator = pow10(rate.scale())
+                    .checked_mul(100)
+                    .and_then(|hundred| hundred.checked_add(rate.mantissa()))
+                    .and_then(|base| base.checked_mul(pow10(price.scale())));
+                match (numerator, denominator) {
+                    (Some(numerator), Some(denominator)) => {
+                        let tax = div_round(numerator, denominator, self.rounding);
+                        Ok((gross - tax, tax))
+                    }
+                    _ => Err(InvoiceError::Overflow),
+                }
+            }
+        }
+    }
+}
+
+impl Calculation {
+    fn totals(
+        &self,
+        cash_increment: Option<i64>,
+        mode: RoundingMode,
+    ) -> Result<Totals, InvoiceError> {
+        let net: i128 = self.groups.iter().map(|&(_, net, _)| net).sum();
+        let tax: i128 = self.groups.iter().map(|&(_, _, tax)| tax).sum();
+        let taxes = self
+            .groups
+            .iter()
+            .map(|&(rate, net, tax)| {
+                Ok(TaxLine {
+                    rate,
+                    net: to_currency(self.symbol, net)?,
+                    tax: to_currency(self.symbol, tax)?,
+                })
+            })
+            .collect::<Result<Vec<_>, InvoiceError>>()?;
+        let total = to_currency(self.symbol, net + tax)?;
+        let payable = match cash_increment {
+            Some(increment) if increment <= 0 => return Err(InvoiceError::InvalidCashIncrement),
+            Some(increment) => total
+                .round_to_increment(increment, mode)
+                .ok_or(InvoiceError::Overflow)?,
+            None => total,
+        };
+        Ok(Totals {
+            discount: to_currency(self.symbol, self.undiscounted - self.discounted)?,
+            subtotal: to_currency(self.symbol, net)?,
+            taxes,
+            tax: to_currency(self.symbol, tax)?,
+            total,
+            cash_rounding: to_currency(
+                self.symbol,
+                i128::from(payable.value) - i128::from(total.value),
+            )?,
+            payable,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dollars(value: i64) -> Currency {
+        Currency::from(('$', value))
+    }
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn nickels(mode: TaxMode, strategy: RoundingStrategy) -> Totals {
+        (0..3)
+            .fold(Invoice::new(), |invoice, _| {
+                invoice.line(InvoiceLine::new("Nickel", 1, dollars(5)))
+            })
+            .tax_rate(dec("10"))
+            .tax_mode(mode)
+            .strategy(strategy)
+            .totals()
+            .unwrap()
+    }
+
+    #[test]
+    fn rounding_strategies() {
+        let per_line = nickels(TaxMode::Exclusive, RoundingStrategy::PerLine);
+        assert_eq!(per_line.tax, dollars(3));
+        assert_eq!(per_line.total, dollars(18));
+        let per_total = nickels(TaxMode::Exclusive, RoundingStrategy::PerTotal);
+        assert_eq!(per_total.tax, dollars(2));
+        assert_eq!(per_total.total, dollars(17));
+    }
+
+    #[test]
+    fn tax_inclusive() {
+        let invoice = Invoice::new()
+            .tax_mode(TaxMode::Inclusive)
+            .tax_rate(dec("19"))
+            .line(InvoiceLine::new("Book", 1, dollars(11900)));
+        let totals = invoice.totals().unwrap();
+        assert_eq!(totals.subtotal, dollars(10000));
+        assert_eq!(totals.tax, dollars(1900));
+        assert_eq!(totals.total, dollars(11900));
+
+        // 5¢ gross contain 0.4545¢ tax
+        let per_line = nickels(TaxMode::Inclusive, RoundingStrategy::PerLine);
+        assert_eq!(per_line.tax, dollars(0));
+        assert_eq!(per_line.total, dollars(15));
+        let per_total = nickels(TaxMode::Inclusive, RoundingStrategy::PerTotal);
+        assert_eq!(per_total.tax, dollars(1));
+        assert_eq!(per_total.subtotal, dollars(14));
+        assert_eq!(per_total.total, dollars(15));
+    }
+
+    #[test]
+    fn discounts() {
+        let invoice = Invoice::new()
+            .discount(dec("50"))
+            .line(InvoiceLine::new("A", 2, dollars(1000)).discount(Discount::Amount(dollars(500))))
+            .line(InvoiceLine::new("B", 1, dollars(999)).discount(Discount::Percent(dec("100"))))
+            .line(InvoiceLine::new("C", 1, dollars(100)).discount(Discount::Amount(dollars(500))));
+        let totals = invoice.totals().unwrap();
+        assert_eq!(totals.subtotal, dollars(750));
+        assert_eq!(totals.discount, dollars(2349));
+    }
+
+    #[test]
+    fn per_total_discount_reconciles() {
+        // every line is 4.5¢ after the discount, 15¢ per line but 14¢ as a total
+        let invoice = (0..3)
+            .fold(Invoice::new(), |invoice, _| {
+                invoice.line(InvoiceLine::new("Nickel", 1, dollars(5)))
+            })
+            .discount(dec("10"))
+            .strategy(RoundingStrategy::PerTotal);
+        let totals = invoice.totals().unwrap();
+        assert_eq!(totals.subtotal, dollars(14));
+        assert_eq!(totals.discount, dollars(1));
+        assert_eq!(totals.subtotal + totals.discount, dollars(15));
+        assert_eq!(totals.total, totals.subtotal + totals.tax);
+
+        let per_line = invoice
+            .strategy(RoundingStrategy::PerLine)
+            .totals()
+            .unwrap();
+        assert_eq!(per_line.subtotal, dollars(15));
+        assert_eq!(per_line.discount, dollars(0));
+    }
+
+    #[test]
+    fn refund_lines() {
+        let invoice = Invoice::new()
+            .tax_rate(dec("10"))
+            .line(InvoiceLine::new("Widget", 2, dollars(1000)))
+            .line(InvoiceLine::new("Returned widget", 1, dollars(-1000)))
+            .line(
+                InvoiceLine::new("Returned gadget", 1, dollars(-500))
+                    .discount(Discount::Percent(dec("10"))),
+            );
+        let totals = invoice.totals().unwrap();
+        assert_eq!(totals.subtotal, dollars(550));
+        assert_eq!(totals.discount, dollars(-50));
+        assert_eq!(totals.tax, dollars(55));
+        assert_eq!(totals.total, dollars(605));
+
+        let refund =
+            Invoice::new()
+                .discount(dec("50"))
+                .line(InvoiceLine::new("Refund", 1, dollars(-1000)));
+        assert_eq!(refund.totals().unwrap().total, dollars(-500));
+    }
+
+    #[test]
+    fn tax_groups() {
+        let invoice = Invoice::new()
+            .tax_rate(dec("19"))
+            .line(InvoiceLine::new("A", 1, dollars(1000)).tax_rate(dec("7.0")))
+            .line(InvoiceLine::new("B", 1, dollars(1000)))
+            .line(InvoiceLine::new("C", 1, dollars(1000)).tax_rate(dec("7")));
+        let totals = invoice.totals().unwrap();
+        assert_eq!(totals.taxes.len(), 2);
+        assert_eq!(totals.taxes[0].net, dollars(2000));
+        assert_eq!(totals.taxes[0].tax, dollars(140));
+        assert_eq!(totals.taxes[1].tax, dollars(190));
+        assert_eq!(totals.total, dollars(3330));
+    }
+
+    #[test]
+    fn errors() {
+        let mixed = Invoice::new()
+            .line(InvoiceLine::new("A", 1, dollars(1000)))
+            .line(InvoiceLine::new("B", 1, Currency::from(('€', 1000))));
+        assert_eq!(mixed.totals(), Err(InvoiceError::CurrencyMismatch));
+        let negative =
+            Invoice::new()
+                .tax_rate(dec("-1"))
+                .line(InvoiceLine::new("A", 1, dollars(1000)));
+        assert_eq!(negative.totals(), Err(InvoiceError::InvalidTaxRate));
+        assert_eq!(Invoice::new().totals().unwrap().total, Currency::from(0));
+    }
+
+    #[test]
+    fn cash_rounding() {
+        let invoice = Invoice::new()
+            .tax_rate(dec("8.1"))
+            .cash_increment(5)
+            .line(InvoiceLine::new("Kaffee", 2, Currency::from(420)));
+        let totals = invoice.totals().unwrap();
+        assert_eq!(totals.total, Currency::from(908));
+        assert_eq!(totals.cash_rounding, Currency::from(2));
+        assert_eq!(totals.payable, Currency::from(910));
+        let rendered = invoice.render().unwrap();
+        let footer: Vec<&str> = rendered.lines().rev().take(3).map(str::trim).collect();
+        assert_eq!(
+            footer,
+            vec![
+                "Payable in cash    9.10",
+                "Cash rounding    0.02",
+                "Total    9.08"
+            ]
+        );
+
+        let without = Invoice::new().line(InvoiceLine::new("Kaffee", 1, Currency::from(421)));
+        assert_eq!(without.totals().unwrap().payable, Currency::from(421));
+        assert_eq!(
+            without.cash_increment(0).totals(),
+            Err(InvoiceError::InvalidCashIncrement)
+        );
+    }
+
+    #[test]
+    fn render_extreme_discount() {
+        let invoice = Invoice::new().line(
+            InvoiceLine::new("Refund", 1, dollars(i64::MIN))
+                .discount(Discount::Amount(dollars(i64::MIN))),
+        );
+        assert_eq!(invoice.totals().unwrap().discount, dollars(i64::MIN));
+        let rendered = invoice.render().unwrap();
+        // the discount column and the footer both show the magnitude of `i64::MIN`
+        assert_eq!(rendered.matches(" $92233720368547758.08").count(), 2);
+        assert_eq!(negated(dollars(500)), "-$5.00");
+        assert_eq!(negated(dollars(-500)), "$5.00");
+        assert_eq!(negated(dollars(0)), "$0.00");
+    }
+
+    #[test]
+    fn render() {
+        let invoice = Invoice::new()
+            .tax_rate(dec("19"))
+            .line(InvoiceLine::new("Widget", 3, dollars(1999)))
+            .line(
+                InvoiceLine::new("Gadget", 1, dollars(500))
+                    .discount(Discount::Percent(dec("10")))
+                    .tax_rate(dec("7")),
+            );
+        let expected = "\
+Description  Qty  Unit price  Discount  Tax  Amount
+---------------------------------------------------
+Widget         3      $19.99            19%  $59.97
+Gadget         1       $5.00       10%   7%   $4.50
+---------------------------------------------------
+                                   Discount  -$0.50
+                                   Subtotal  $64.47
+                                     Tax 7%   $0.32
+                                    Tax 19%  $11.39
+                                      Total  $76.18";
+        assert_eq!(invoice.render().unwrap(), expected);
+    }
+}
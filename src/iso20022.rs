@@ -0,0 +1,551 @@
+//! Amounts as used in ISO 20022 payment messages, e.g. SEPA credit transfers.
+//!
+//! [`ActiveOrHistoricCurrencyAndAmount`] is the amount with a `Ccy` attribute found in elements
+//! like `<InstdAmt Ccy="EUR">12.50</InstdAmt>`. The schema restricts it to non-negative numbers
+//! with at most [`MAX_TOTAL_DIGITS`] digits, [`MAX_FRACTION_DIGITS`] of them after the decimal
+//! point, and three letter codes.
+//!
+//! The text of the element is an XML Schema decimal: a dot as decimal point and no grouping,
+//! unlike the output of [`Postfix`](crate::Postfix). [`format()`] and [`parse()`] convert
+//! between [`Currency`] and such elements and reject anything the schema would reject. SEPA
+//! payment files restrict amounts further, see
+//! [`validate_sepa`](ActiveOrHistoricCurrencyAndAmount::validate_sepa).
+//!
+//! # Examples
+//! ```
+//! use std::convert::TryFrom;
+//! use claude::{decimal::Decimal, iso20022::{self, ActiveOrHistoricCurrencyAndAmount}, Currency};
+//!
+//! let amount = ActiveOrHistoricCurrencyAndAmount::try_from(Currency::from(('€', 1250))).unwrap();
+//! assert_eq!(amount.ccy(), "EUR");
+//! assert_eq!(amount.amount(), Decimal::new(1250, 2));
+//! assert_eq!(Currency::try_from(&amount), Ok(Currency::from(('€', 1250))));
+//!
+//! let xml = iso20022::format("InstdAmt", Currency::from(('€', 4711))).unwrap();
+//! assert_eq!(xml, r#"<InstdAmt Ccy="EUR">47.11</InstdAmt>"#);
+//! assert_eq!(iso20022::parse("InstdAmt", &xml), Ok(Currency::from(('€', 4711))));
+//! ```
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use crate::decimal::Decimal;
+use crate::info::CurrencyInfo;
+use crate::rounding::RoundingMode;
+use crate::Currency;
+
+/// Largest number of digits of an amount.
+pub const MAX_TOTAL_DIGITS: u32 = 18;
+/// Largest number of digits after the decimal point.
+pub const MAX_FRACTION_DIGITS: u32 = 5;
+/// Largest number of digits after the decimal point in SEPA payment files.
+pub const SEPA_MAX_FRACTION_DIGITS: u32 = 2;
+/// Largest amount in SEPA payment files, `999999999.99`.
+pub const SEPA_MAX_AMOUNT: i64 = 99_999_999_999;
+
+/// Reasons why an amount is not a valid ISO 20022 amount or cannot be converted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Iso20022Error {
+    /// The code is not three upper case letters.
+    InvalidCurrencyCode,
+    /// The symbol has no ISO 4217 code, or the code has no symbol.
+    UnknownCurrency,
+    /// The amount is negative.
+    Negative,
+    /// The amount has more than [`MAX_TOTAL_DIGITS`] digits or more than [`MAX_FRACTION_DIGITS`]
+    /// after the decimal point.
+    TooManyDigits,
+    /// The amount has fractions of the smallest unit, converting would lose precision.
+    PrecisionLoss,
+    /// The amount does not fit.
+    Overflow,
+    /// The text is not a decimal number with a dot as decimal point.
+    InvalidAmount,
+    /// The XML is not a single amount element with a `Ccy` attribute.
+    InvalidElement,
+    /// SEPA payment files only allow euros.
+    CurrencyNotAllowed,
+    /// The amount is outside the range SEPA payment files allow, `0.01` to `999999999.99`.
+    OutOfRange,
+}
+
+impl fmt::Display for Iso20022Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Iso20022Error::InvalidCurrencyCode => "currency code must be three upper case letters",
+            Iso20022Error::UnknownCurrency => "currency has no ISO 4217 code and symbol",
+            Iso20022Error::Negative => "amount must not be negative",
+            Iso20022Error::TooManyDigits => "amount has too many digits",
+            Iso20022Error::PrecisionLoss => "amount has fractions of the smallest unit",
+            Iso20022Error::Overflow => "amount does not fit",
+            Iso20022Error::InvalidAmount => "not a decimal number with a dot as decimal point",
+            Iso20022Error::InvalidElement => "not an amount element with a Ccy attribute",
+            Iso20022Error::CurrencyNotAllowed => "SEPA only allows euros",
+            Iso20022Error::OutOfRange => "amount out of the SEPA range",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for Iso20022Error {}
+
+/// A non-negative amount with a three letter currency code.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ActiveOrHistoricCurrencyAndAmount {
+    ccy: String,
+    amount: Decimal,
+}
+
+fn is_xml_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r')
+}
+
+/// Parses the lexical form of an XML Schema decimal, e.g. `47.11`, `+0.5`, `.5` or `047.`,
+/// surrounded by optional whitespace.
+fn parse_decimal(text: &str) -> Result<Decimal, Iso20022Error> {
+    let text = text.trim_matches(is_xml_whitespace);
+    let (negative, digits) = match text.as_bytes().first() {
+        Some(b'+') => (false, &text[1..]),
+        Some(b'-') => (true, &text[1..]),
+        _ => (false, text),
+    };
+    let (int, frac) = match digits.find('.') {
+        Some(pos) => (&digits[..pos], &digits[pos + 1..]),
+        None => (digits, ""),
+    };
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (int.is_empty() && frac.is_empty()) || !all_digits(int) || !all_digits(frac) {
+        return Err(Iso20022Error::InvalidAmount);
+    }
+
+    // leading and trailing zeros are not significant and may be arbitrarily many
+    let int = int.trim_start_matches('0');
+    let frac = frac.trim_end_matches('0');
+    if int.len() + frac.len() > MAX_TOTAL_DIGITS as usize
+        || frac.len() > MAX_FRACTION_DIGITS as usize
+    {
+        return Err(Iso20022Error::TooManyDigits);
+    }
+    let mantissa = int
+        .bytes()
+        .chain(frac.bytes())
+        .fold(0i128, |mantissa, digit| {
+            mantissa * 10 + i128::from(digit - b'0')
+        });
+    if negative && mantissa != 0 {
+        return Err(Iso20022Error::Negative);
+    }
+    Ok(Decimal::new(mantissa, frac.len() as u32))
+}
+
+fn digits(mut mantissa: i128) -> u32 {
+    let mut digits = 1;
+    while mantissa >= 10 {
+        mantissa /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+impl ActiveOrHistoricCurrencyAndAmount {
+    /// Checks `ccy` and `amount` against the restrictions of the schema.
+    ///
+    /// The code only needs to look like one, historic codes are allowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{decimal::Decimal, iso20022::*};
+    ///
+    /// assert!(ActiveOrHistoricCurrencyAndAmount::new("DEM", Decimal::new(100_000, 5)).is_ok());
+    /// assert_eq!(
+    ///     ActiveOrHistoricCurrencyAndAmount::new("EUR", Decimal::new(1, 6)),
+    ///     Err(Iso20022Error::TooManyDigits)
+    /// );
+    /// ```
+    pub fn new(
+        ccy: &str,
+        amount: Decimal,
+    ) -> Result<ActiveOrHistoricCurrencyAndAmount, Iso20022Error> {
+        if ccy.len() != 3 || !ccy.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(Iso20022Error::InvalidCurrencyCode);
+        }
+        if amount.is_negative() {
+            return Err(Iso20022Error::Negative);
+        }
+        let canonical = amount.normalized();
+        if canonical.scale() > MAX_FRACTION_DIGITS
+            || digits(canonical.mantissa()) > MAX_TOTAL_DIGITS
+        {
+            return Err(Iso20022Error::TooManyDigits);
+        }
+        Ok(ActiveOrHistoricCurrencyAndAmount {
+            ccy: ccy.to_string(),
+            amount,
+        })
+    }
+
+    /// The three letter currency code, the `Ccy` attribute.
+    pub fn ccy(&self) -> &str {
+        &self.ccy
+    }
+
+    /// The amount.
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    /// Parses the text of an amount element, e.g. `47.11`.
+    ///
+    /// Accepts exactly the XML Schema decimals within the restrictions of the schema, commas,
+    /// grouping and exponents are rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::iso20022::{ActiveOrHistoricCurrencyAndAmount, Iso20022Error};
+    ///
+    /// let amount = ActiveOrHistoricCurrencyAndAmount::parse("EUR", "47.11").unwrap();
+    /// assert_eq!(amount.to_string(), "47.11");
+    /// assert_eq!(
+    ///     ActiveOrHistoricCurrencyAndAmount::parse("EUR", "47,11"),
+    ///     Err(Iso20022Error::InvalidAmount)
+    /// );
+    /// ```
+    pub fn parse(
+        ccy: &str,
+        text: &str,
+    ) -> Result<ActiveOrHistoricCurrencyAndAmount, Iso20022Error> {
+        ActiveOrHistoricCurrencyAndAmount::new(ccy, parse_decimal(text)?)
+    }
+
+    /// Parses an element like `<InstdAmt Ccy="EUR">47.11</InstdAmt>` with the given name.
+    ///
+    /// The element must have the `Ccy` attribute and no other, and no child elements.
+    pub fn from_xml(
+        element: &str,
+        xml: &str,
+    ) -> Result<ActiveOrHistoricCurrencyAndAmount, Iso20022Error> {
+        let (ccy, text) = split_element(element, xml).ok_or(Iso20022Error::InvalidElement)?;
+        ActiveOrHistoricCurrencyAndAmount::parse(ccy, text)
+    }
+
+    /// Formats as an element with the given name, e.g. `<InstdAmt Ccy="EUR">47.11</InstdAmt>`.
+    pub fn to_xml(&self, element: &str) -> String {
+        format!(
+            "<{element} Ccy=\"{ccy}\">{amount}</{element}>",
+            element = element,
+            ccy = self.ccy,
+            amount = self
+        )
+    }
+
+    /// Checks the additional restrictions of SEPA payment files: euros only, at most two digits
+    /// after the decimal point and an amount from `0.01` to `999999999.99`.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::iso20022::{ActiveOrHistoricCurrencyAndAmount, Iso20022Error};
+    ///
+    /// let amount = |ccy, text| ActiveOrHistoricCurrencyAndAmount::parse(ccy, text).unwrap();
+    /// assert_eq!(amount("EUR", "47.11").validate_sepa(), Ok(()));
+    /// assert_eq!(amount("EUR", "0.00").validate_sepa(), Err(Iso20022Error::OutOfRange));
+    /// assert_eq!(amount("EUR", "0.001").validate_sepa(), Err(Iso20022Error::TooManyDigits));
+    /// assert_eq!(amount("CHF", "1").validate_sepa(), Err(Iso20022Error::CurrencyNotAllowed));
+    /// ```
+    pub fn validate_sepa(&self) -> Result<(), Iso20022Error> {
+        if self.ccy != "EUR" {
+            return Err(Iso20022Error::CurrencyNotAllowed);
+        }
+        if self.amount.normalized().scale() > SEPA_MAX_FRACTION_DIGITS {
+            return Err(Iso20022Error::TooManyDigits);
+        }
+        let cents = self
+            .amount
+            .rescale(SEPA_MAX_FRACTION_DIGITS, RoundingMode::Down)
+            .ok_or(Iso20022Error::OutOfRange)?
+            .mantissa();
+        if cents < 1 || cents > i128::from(SEPA_MAX_AMOUNT) {
+            return Err(Iso20022Error::OutOfRange);
+        }
+        Ok(())
+    }
+}
+
+/// Splits `<element Ccy="...">text</element>` into the code and the text.
+fn split_element<'a>(element: &str, xml: &'a str) -> Option<(&'a str, &'a str)> {
+    let rest = xml
+        .trim_matches(is_xml_whitespace)
+        .strip_prefix('<')?
+        .strip_prefix(element)?;
+    let attribute = rest.trim_start_matches(is_xml_whitespace);
+    if attribute.len() == rest.len() {
+        return None;
+    }
+    let value = attribute
+        .strip_prefix("Ccy")?
+        .trim_start_matches(is_xml_whitespace)
+        .strip_prefix('=')?
+        .trim_start_matches(is_xml_whitespace);
+    let quote = value.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let value = &value[1..];
+    let end = value.find(quote)?;
+    let ccy = &value[..end];
+    let content = value[end + 1..]
+        .trim_start_matches(is_xml_whitespace)
+        .strip_prefix('>')?;
+    let end = content.find('<')?;
+    let text = &content[..end];
+    let closing = content[end..]
+        .strip_prefix("</")?
+        .strip_prefix(element)?
+        .trim_start_matches(is_xml_whitespace);
+    if closing != ">" {
+        return None;
+    }
+    Some((ccy, text))
+}
+
+/// Writes the amount as XML Schema decimal with at least two digits after the decimal point,
+/// e.g. `47.10` or `0.12345`.
+impl fmt::Display for ActiveOrHistoricCurrencyAndAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let canonical = self.amount.normalized();
+        match canonical.rescale(canonical.scale().max(2), RoundingMode::Down) {
+            Some(padded) => padded.fmt(f),
+            None => canonical.fmt(f),
+        }
+    }
+}
+
+/// Formats `money` as an amount element with the given name, e.g.
+/// `<InstdAmt Ccy="EUR">47.11</InstdAmt>`.
+///
+/// Fails if the currency has no ISO 4217 code or the amount is negative or too large.
+pub fn format(element: &str, money: Currency) -> Result<String, Iso20022Error> {
+    ActiveOrHistoricCurrencyAndAmount::try_from(money).map(|amount| amount.to_xml(element))
+}
+
+/// Parses an amount element with the given name, e.g. `<InstdAmt Ccy="EUR">47.11</InstdAmt>`.
+///
+/// Fails if the element violates the schema or the amount has fractions of the smallest unit.
+pub fn parse(element: &str, xml: &str) -> Result<Currency, Iso20022Error> {
+    Currency::try_from(ActiveOrHistoricCurrencyAndAmount::from_xml(element, xml)?)
+}
+
+impl TryFrom<Currency> for ActiveOrHistoricCurrencyAndAmount {
+    type Error = Iso20022Error;
+
+    fn try_from(money: Currency) -> Result<ActiveOrHistoricCurrencyAndAmount, Iso20022Error> {
+        let info = money.info().ok_or(Iso20022Error::UnknownCurrency)?;
+        ActiveOrHistoricCurrencyAndAmount::new(info.code(), Decimal::new(money.value.into(), 2))
+    }
+}
+
+impl<'a> TryFrom<&'a ActiveOrHistoricCurrencyAndAmount> for Currency {
+    type Error = Iso20022Error;
+
+    fn try_from(amount: &'a ActiveOrHistoricCurrencyAndAmount) -> Result<Currency, Iso20022Error> {
+        let symbol = CurrencyInfo::from_code(&amount.ccy)
+            .and_then(CurrencyInfo::symbol)
+            .ok_or(Iso20022Error::UnknownCurrency)?;
+        let minor = amount
+            .amount
+            .rescale(2, RoundingMode::Down)
+            .ok_or(Iso20022Error::Overflow)?;
+        if minor != amount.amount {
+            return Err(Iso20022Error::PrecisionLoss);
+        }
+        let value = i64::try_from(minor.mantissa()).map_err(|_| Iso20022Error::Overflow)?;
+        Ok(Currency::from((symbol, value)))
+    }
+}
+
+impl TryFrom<ActiveOrHistoricCurrencyAndAmount> for Currency {
+    type Error = Iso20022Error;
+
+    fn try_from(amount: ActiveOrHistoricCurrencyAndAmount) -> Result<Currency, Iso20022Error> {
+        Currency::try_from(&amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(ccy: &str, s: &str) -> Result<ActiveOrHistoricCurrencyAndAmount, Iso20022Error> {
+        ActiveOrHistoricCurrencyAndAmount::new(ccy, s.parse().unwrap())
+    }
+
+    #[test]
+    fn validation() {
+        assert!(amount("EUR", "0").is_ok());
+        assert!(amount("EUR", "999999999999999999").is_ok());
+        assert!(amount("EUR", "9999999999999.99999").is_ok());
+        // trailing zeros are not significant
+        assert!(amount("EUR", "1.2300000").is_ok());
+        assert_eq!(
+            amount("EUR", "9999999999999999999"),
+            Err(Iso20022Error::TooManyDigits)
+        );
+        assert_eq!(amount("EUR", "0.000001"), Err(Iso20022Error::TooManyDigits));
+        assert_eq!(amount("EUR", "-0.01"), Err(Iso20022Error::Negative));
+        for ccy in &["", "EU", "EURO", "eur", "E1R", "€UR"] {
+            assert_eq!(amount(ccy, "1"), Err(Iso20022Error::InvalidCurrencyCode));
+        }
+    }
+
+    #[test]
+    fn lexical_forms() {
+        let text = |s: &str| {
+            ActiveOrHistoricCurrencyAndAmount::parse("EUR", s).map(|amount| amount.to_string())
+        };
+        assert_eq!(text("47.11"), Ok("47.11".to_string()));
+        assert_eq!(text("47.1"), Ok("47.10".to_string()));
+        assert_eq!(text("47"), Ok("47.00".to_string()));
+        assert_eq!(text("+0.5"), Ok("0.50".to_string()));
+        assert_eq!(text(".5"), Ok("0.50".to_string()));
+        assert_eq!(text("5."), Ok("5.00".to_string()));
+        assert_eq!(text("-0"), Ok("0.00".to_string()));
+        assert_eq!(text(" 0.12345\n"), Ok("0.12345".to_string()));
+        assert_eq!(
+            text("000000000000000000001.1000000000"),
+            Ok("1.10".to_string())
+        );
+
+        for invalid in &[
+            "", ".", "+", "47,11", "1,000.00", "1 000", "1e3", "0x10", "1.2.3", "€1",
+        ] {
+            assert_eq!(
+                text(invalid),
+                Err(Iso20022Error::InvalidAmount),
+                "{}",
+                invalid
+            );
+        }
+        assert_eq!(text("-0.01"), Err(Iso20022Error::Negative));
+        assert_eq!(text("0.000001"), Err(Iso20022Error::TooManyDigits));
+        assert_eq!(
+            text("1234567890123456789"),
+            Err(Iso20022Error::TooManyDigits)
+        );
+    }
+
+    #[test]
+    fn xml() {
+        let money = Currency::from(('€', 4711));
+        assert_eq!(
+            format("InstdAmt", money),
+            Ok(r#"<InstdAmt Ccy="EUR">47.11</InstdAmt>"#.to_string())
+        );
+        assert_eq!(
+            format("Amt", Currency::from(('£', 5))),
+            Ok(r#"<Amt Ccy="GBP">0.05</Amt>"#.to_string())
+        );
+        for xml in &[
+            r#"<InstdAmt Ccy="EUR">47.11</InstdAmt>"#,
+            r#"<InstdAmt Ccy='EUR'>47.11</InstdAmt>"#,
+            "<InstdAmt\n  Ccy = \"EUR\" >\n 47.110 </InstdAmt >",
+        ] {
+            assert_eq!(parse("InstdAmt", xml), Ok(money), "{}", xml);
+        }
+        for xml in &[
+            r#"<InstdAmt>47.11</InstdAmt>"#,
+            r#"<InstdAmt Ccy="EUR">47.11</Amt>"#,
+            r#"<Amt Ccy="EUR">47.11</Amt>"#,
+            r#"<InstdAmtX Ccy="EUR">47.11</InstdAmtX>"#,
+            r#"<InstdAmt Ccy="EUR" Foo="1">47.11</InstdAmt>"#,
+            r#"<InstdAmt Ccy="EUR'>47.11</InstdAmt>"#,
+            r#"<InstdAmt Ccy="EUR"><V>47.11</V></InstdAmt>"#,
+            r#"<InstdAmt Ccy="EUR">47.11</InstdAmt><X/>"#,
+        ] {
+            assert_eq!(
+                parse("InstdAmt", xml),
+                Err(Iso20022Error::InvalidElement),
+                "{}",
+                xml
+            );
+        }
+        assert_eq!(
+            parse("InstdAmt", r#"<InstdAmt Ccy="EUR">47,11</InstdAmt>"#),
+            Err(Iso20022Error::InvalidAmount)
+        );
+        assert_eq!(
+            parse("InstdAmt", r#"<InstdAmt Ccy="eur">47.11</InstdAmt>"#),
+            Err(Iso20022Error::InvalidCurrencyCode)
+        );
+        assert_eq!(
+            parse("InstdAmt", r#"<InstdAmt Ccy="EUR">47.111</InstdAmt>"#),
+            Err(Iso20022Error::PrecisionLoss)
+        );
+        assert_eq!(
+            format("InstdAmt", Currency::from(('€', -1))),
+            Err(Iso20022Error::Negative)
+        );
+    }
+
+    #[test]
+    fn sepa() {
+        let sepa = |text: &str| amount("EUR", text).unwrap().validate_sepa();
+        assert_eq!(sepa("0.01"), Ok(()));
+        assert_eq!(sepa("999999999.99"), Ok(()));
+        assert_eq!(sepa("999999999.990"), Ok(()));
+        assert_eq!(sepa("1000000000"), Err(Iso20022Error::OutOfRange));
+        assert_eq!(sepa("0"), Err(Iso20022Error::OutOfRange));
+        assert_eq!(sepa("0.005"), Err(Iso20022Error::TooManyDigits));
+    }
+
+    #[test]
+    fn round_trip() {
+        for &value in &[0, 1, 10, 99, 100, 4711, 123_456_789, 99_999_999_999_999_999] {
+            for &symbol in &['€', '$', '£', '¥', '₹'] {
+                let money = Currency::from((symbol, value));
+                let xml = format("InstdAmt", money).unwrap();
+                assert_eq!(parse("InstdAmt", &xml), Ok(money), "{}", xml);
+            }
+        }
+    }
+
+    #[test]
+    fn conversion() {
+        let converted =
+            ActiveOrHistoricCurrencyAndAmount::try_from(Currency::from(('¥', 5))).unwrap();
+        assert_eq!(converted.ccy(), "JPY");
+        assert_eq!(converted.amount().to_string(), "0.05");
+        assert_eq!(
+            Currency::try_from(&amount("GBP", "12.5").unwrap()),
+            Ok(Currency::from(('£', 1250)))
+        );
+        assert_eq!(
+            Currency::try_from(amount("USD", "1.00000").unwrap()),
+            Ok(Currency::from(('$', 100)))
+        );
+    }
+
+    #[test]
+    fn conversion_errors() {
+        assert_eq!(
+            Currency::try_from(amount("EUR", "0.001").unwrap()),
+            Err(Iso20022Error::PrecisionLoss)
+        );
+        assert_eq!(
+            Currency::try_from(amount("DEM", "1").unwrap()),
+            Err(Iso20022Error::UnknownCurrency)
+        );
+        assert_eq!(
+            Currency::try_from(amount("EUR", "999999999999999999").unwrap()),
+            Err(Iso20022Error::Overflow)
+        );
+        assert_eq!(
+            ActiveOrHistoricCurrencyAndAmount::try_from(Currency::from(('€', -1))),
+            Err(Iso20022Error::Negative)
+        );
+        assert_eq!(
+            ActiveOrHistoricCurrencyAndAmount::try_from(Currency::from(100)),
+            Err(Iso20022Error::UnknownCurrency)
+        );
+        assert_eq!(
+            ActiveOrHistoricCurrencyAndAmount::try_from(Currency::from(('$', i64::MAX))),
+            Err(Iso20022Error::TooManyDigits)
+        );
+    }
+}
@@ -22,14 +22,42 @@ extern crate serde_derive;
 /// Symbol of a currency
 pub type Symbol = char;
 
+pub mod amount;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod batch;
+pub mod cash;
+pub mod change;
+pub mod cmp;
+pub mod column;
+pub mod compact;
+pub mod decimal;
 pub mod display;
+pub mod finance;
+pub mod info;
+pub mod invoice;
+pub mod iso20022;
+pub mod locale;
 pub mod math;
+#[cfg(feature = "parsing")]
+pub mod parse;
+pub mod pricing;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+pub mod quantity;
+pub mod range;
+pub mod ratio;
+pub mod rounding;
+pub mod sum;
+pub mod swift;
+pub mod unit_price;
+pub mod words;
 
 /// Represents currency through an optional symbol and amount of coin.
 ///
 /// Each 100 coins results in a banknote. (100 is formatted as 1.00)
 /// The currency will be formatted as such: `Currency(Some('$'), 432)` ==> "$4.32"
-#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialization", derive(Serialize))]
 pub struct Currency {
     /// Currency symbol
@@ -67,6 +95,11 @@ impl Currency {
     /// If the currency is intended to be a negative amount, ensure the '-' is the first symbol
     /// in the string.
     /// The Regex recognizes European notation (€1,00)
+    /// Unicode digits, fullwidth characters and non-breaking spaces are normalized first, see
+    /// [`parse::normalize`].
+    ///
+    /// `.` and `,` are treated interchangeably, use [`Currency::from_str_with_locale`] or
+    /// [`str::parse`] to tell decimal and group separators apart.
     ///
     /// # Examples
     /// ```
@@ -77,52 +110,57 @@ impl Currency {
     /// assert!(Currency::from_string("424.44") == Some(Currency{symbol: None,      value: 42444}));
     /// assert!(Currency::from_string("£12,00") == Some(Currency{symbol: Some('£'), value:  1200}));
     /// assert!(Currency::from_string("¥12")    == Some(Currency{symbol: Some('¥'), value:  1200}));
+    /// assert!(Currency::from_string("＄１２．００") == Some(Currency{symbol: Some('$'), value: 1200}));
     /// ```
     #[cfg(feature = "parsing")]
     pub fn from_string(s: &str) -> Option<Currency> {
         use regex::Regex;
 
-        // Shadow s with a trimmed version
-        let s = s.trim();
-        let re =
-            Regex::new(r"^(?:\b|(-)?)(\p{Currency_Symbol})?((?:(?:\d{1,3}[\.,])+\d{3})|\d+)(?:[\.,](\d{2}))?\b$")
-            .unwrap();
-
-        // Used to negate the final result if the regex matches a negative
-        let mut multiplier = 1;
-        let mut sign: Option<Symbol> = None;
-        let coin_str: String;
-
-        match re.captures(s) {
-            None => return None,
-            Some(caps) => {
-                if caps.get(1).is_some() {
-                    multiplier = -1;
-                }
-
-                if caps.get(2).is_some() {
-                    if multiplier < 0 {
-                        sign = Some(s.chars().nth(1).unwrap());
-                    } else {
-                        sign = Some(s.chars().next().unwrap());
-                    }
-                }
-                coin_str = caps
-                    .get(3)
-                    .map(|m| m.as_str())
-                    .unwrap()
-                    .replace(['.', ','], "")
-                    + caps.get(4).map(|m| m.as_str()).unwrap_or("00");
-            }
+        thread_local! {
+            static RE: Regex =
+                Regex::new(r"^(?:\b|(-)?)(\p{Currency_Symbol})?((?:(?:\d{1,3}[\., ])+\d{3})|\d+)(?:[\.,](\d{2}))?\b$")
+                .unwrap();
         }
 
-        if let Ok(coin) = coin_str.parse::<i64>() {
-            return Some(Currency {
+        // Shadow s with a normalized and trimmed version
+        let normalized = parse::normalize(s);
+        let s = normalized.trim();
+
+        RE.with(|re| {
+            let caps = re.captures(s)?;
+            let sign: Option<Symbol> = caps.get(2).and_then(|m| m.as_str().chars().next());
+
+            // Keep the minus in the digits, so that the magnitude of i64::MIN does not overflow
+            let mut coin_str = String::from(if caps.get(1).is_some() { "-" } else { "" });
+            coin_str.push_str(&caps.get(3)?.as_str().replace(['.', ',', ' '], ""));
+            coin_str.push_str(caps.get(4).map_or("00", |m| m.as_str()));
+
+            coin_str.parse::<i64>().ok().map(|value| Currency {
                 symbol: sign,
-                value: multiplier * coin,
-            });
-        }
-        None
+                value,
+            })
+        })
+    }
+
+    /// Parses a string using the decimal and group separators of the given locale.
+    ///
+    /// Unlike [`Currency::from_string`] this does not confuse decimal and group separators and
+    /// reports why the input was rejected. See [`parse`] for details.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{locale::Locale, parse::ParseError, Currency};
+    ///
+    /// assert_eq!(Currency::from_str_with_locale("1,5", Locale::DeDe), Ok(Currency::from(150)));
+    /// assert_eq!(Currency::from_str_with_locale("€1.234", Locale::DeDe), Ok(Currency::from(('€', 123400))));
+    /// assert_eq!(Currency::from_str_with_locale("1,23,4.00", Locale::EnUs), Err(ParseError::InvalidGrouping));
+    /// ```
+    #[cfg(feature = "parsing")]
+    pub fn from_str_with_locale(
+        s: &str,
+        locale: locale::Locale,
+    ) -> Result<Currency, parse::ParseError> {
+        parse::parse_with_locale(s, locale)
     }
 
     /// Returns an object that implements `Display` for different methods of printing currency.
@@ -135,6 +173,18 @@ impl Currency {
         Prefix { money: self }
     }
 
+    /// Returns an object that implements `Display` by abbreviating large amounts, e.g. `$4.5M`.
+    pub fn compact(&self) -> compact::Compact<'_> {
+        compact::Compact::new(self)
+    }
+
+    /// Returns an object that implements `Display` by spelling out the amount in words.
+    ///
+    /// Use [`Words::cheque`](words::Words::cheque) for the "and 45/100" form.
+    pub fn to_words(&self, language: locale::Language) -> words::Words<'_> {
+        words::Words::new(self, language)
+    }
+
     /// Returns the value as float
     ///
     /// # Warning, do not use this for calculation, this is for displaying only!
@@ -147,10 +197,49 @@ impl Currency {
         self.value
     }
 
+    /// Returns the value without the symbol
+    pub fn amount(&self) -> amount::Amount {
+        amount::Amount(self.value)
+    }
+
     /// Returns the inner symbol
     pub fn symbol(&self) -> Option<Symbol> {
         self.symbol
     }
+
+    /// Metadata of this currency, if its symbol is known.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::Currency;
+    ///
+    /// assert_eq!(Currency::from(('€', 100)).info().unwrap().code(), "EUR");
+    /// assert!(Currency::from(100).info().is_none());
+    /// ```
+    pub fn info(&self) -> Option<&'static info::CurrencyInfo> {
+        self.symbol.and_then(info::CurrencyInfo::from_symbol)
+    }
+}
+
+/// Parses a string, inferring decimal and group separators.
+///
+/// Ambiguous input such as `"1,234"` is rejected, see [`parse`] for the heuristic.
+///
+/// # Examples
+/// ```
+/// use claude::{parse::ParseError, Currency};
+///
+/// assert_eq!("$1,5".parse(), Ok(Currency::from(('$', 150))));
+/// assert_eq!("12.000,99".parse(), Ok(Currency::from(1200099)));
+/// assert_eq!("1.234".parse::<Currency>(), Err(ParseError::Ambiguous));
+/// ```
+#[cfg(feature = "parsing")]
+impl std::str::FromStr for Currency {
+    type Err = parse::ParseError;
+
+    fn from_str(s: &str) -> Result<Currency, parse::ParseError> {
+        parse::parse_inferred(s)
+    }
 }
 
 use std::ops::Deref;
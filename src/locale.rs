@@ -0,0 +1,69 @@
+//! Locales describing how amounts are written in different regions.
+
+/// How the digits of the integer part are grouped.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Grouping {
+    /// Groups of three digits: `1,234,567`
+    Thousands,
+    /// Indian lakh/crore grouping, three digits followed by groups of two: `12,34,567`
+    Indian,
+}
+
+/// Languages amounts can be spelled out in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// English
+    English,
+    /// German
+    German,
+}
+
+/// A locale determines the decimal separator, the group separator and the digit grouping.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// English (United States): `1,234.56`
+    EnUs,
+    /// English (United Kingdom): `1,234.56`
+    EnGb,
+    /// English (India): `1,23,456.78`
+    EnIn,
+    /// German (Germany): `1.234,56`
+    DeDe,
+    /// German (Switzerland): `1'234.56`
+    DeCh,
+}
+
+impl Locale {
+    /// Character separating the integer part from the fractional part.
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Locale::EnUs | Locale::EnGb | Locale::EnIn | Locale::DeCh => '.',
+            Locale::DeDe => ',',
+        }
+    }
+
+    /// Character separating groups of digits in the integer part.
+    pub fn group_separator(self) -> char {
+        match self {
+            Locale::EnUs | Locale::EnGb | Locale::EnIn => ',',
+            Locale::DeDe => '.',
+            Locale::DeCh => '\'',
+        }
+    }
+
+    /// How digits of the integer part are grouped.
+    pub fn grouping(self) -> Grouping {
+        match self {
+            Locale::EnIn => Grouping::Indian,
+            _ => Grouping::Thousands,
+        }
+    }
+
+    /// Language spoken in this locale.
+    pub fn language(self) -> Language {
+        match self {
+            Locale::EnUs | Locale::EnGb | Locale::EnIn => Language::English,
+            Locale::DeDe | Locale::DeCh => Language::German,
+        }
+    }
+}
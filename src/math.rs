@@ -1,28 +1,325 @@
-//! Implementations of standard operators:  `Add`, `Sub`, `Mul`, `Div`
+//! Implementations of standard operators:  `Add`, `Sub`, `Mul`, `Div`, `Rem`, `Neg` and their
+//! assigning forms.
+//!
+//! Currencies only combine with currencies of the same symbol, see [`amount`](crate::amount) for
+//! mixing in values without a symbol.
+//! Every binary operator is also implemented for references, so `&a + &b` works as well as
+//! `a + b`.
+//!
+//! # Overflow
+//!
+//! By default the operators behave like the operators on `i64`: they panic on overflow in debug
+//! builds and wrap around in release builds. `Mul<f64>` saturates at `i64::MIN` and `i64::MAX`,
+//! `Mul<Quantity>` panics on overflow in every build.
+//!
+//! With the `overflow-checks` feature every operator checks for overflow and panics with a clear
+//! message in release builds as well, so ledger code behaves the same in tests and production.
+//!
+//! To handle overflow explicitly use the `checked_*`, `saturating_*` and `wrapping_*` methods.
+//! They follow the same symbol rules as the operators and panic if the symbols differ.
 
+use crate::amount::Amount;
+use crate::quantity::Quantity;
+use crate::rounding::RoundingMode;
 use crate::Currency;
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// Implements `&a op b`, `a op &b` and `&a op &b` in terms of `a op b`.
+macro_rules! forward_ref_binop {
+    (impl $imp:ident, $method:ident for $t:ty, $u:ty) => {
+        impl<'a> $imp<$u> for &'a $t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline]
+            fn $method(self, rhs: $u) -> Self::Output {
+                $imp::$method(*self, rhs)
+            }
+        }
+
+        impl<'a> $imp<&'a $u> for $t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline]
+            fn $method(self, rhs: &'a $u) -> Self::Output {
+                $imp::$method(self, *rhs)
+            }
+        }
+
+        impl<'a, 'b> $imp<&'a $u> for &'b $t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline]
+            fn $method(self, rhs: &'a $u) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+/// Implements `a op= b` and `a op= &b` in terms of `a op b`.
+macro_rules! impl_op_assign {
+    (impl $imp:ident, $method:ident for $t:ty, $u:ty, $op:ident, $op_method:ident) => {
+        impl $imp<$u> for $t {
+            #[inline]
+            fn $method(&mut self, rhs: $u) {
+                *self = $op::$op_method(*self, rhs);
+            }
+        }
+
+        impl<'a> $imp<&'a $u> for $t {
+            #[inline]
+            fn $method(&mut self, rhs: &'a $u) {
+                *self = $op::$op_method(*self, *rhs);
+            }
+        }
+    };
+}
+
+const CHECKED: bool = cfg!(feature = "overflow-checks");
+
+#[cold]
+#[inline(never)]
+fn overflow(operation: &str) -> ! {
+    panic!("Currency overflow: attempt to {} with overflow", operation)
+}
+
+#[inline]
+fn add_values(lhs: i64, rhs: i64) -> i64 {
+    if CHECKED {
+        lhs.checked_add(rhs).unwrap_or_else(|| overflow("add"))
+    } else {
+        lhs + rhs
+    }
+}
+
+#[inline]
+fn sub_values(lhs: i64, rhs: i64) -> i64 {
+    if CHECKED {
+        lhs.checked_sub(rhs).unwrap_or_else(|| overflow("subtract"))
+    } else {
+        lhs - rhs
+    }
+}
+
+#[inline]
+fn mul_values(lhs: i64, rhs: i64) -> i64 {
+    if CHECKED {
+        lhs.checked_mul(rhs).unwrap_or_else(|| overflow("multiply"))
+    } else {
+        lhs * rhs
+    }
+}
+
+#[inline]
+fn div_values(lhs: i64, rhs: i64) -> i64 {
+    if CHECKED && rhs != 0 {
+        lhs.checked_div(rhs).unwrap_or_else(|| overflow("divide"))
+    } else {
+        lhs / rhs
+    }
+}
+
+#[inline]
+fn rem_values(lhs: i64, rhs: i64) -> i64 {
+    if CHECKED && rhs != 0 {
+        lhs.checked_rem(rhs)
+            .unwrap_or_else(|| overflow("calculate the remainder"))
+    } else {
+        lhs % rhs
+    }
+}
+
+#[inline]
+fn neg_value(value: i64) -> i64 {
+    if CHECKED {
+        value.checked_neg().unwrap_or_else(|| overflow("negate"))
+    } else {
+        -value
+    }
+}
+
+#[inline]
+fn float_to_value(value: f64) -> i64 {
+    let rounded = value.round();
+    if CHECKED && !(rounded >= i64::MIN as f64 && rounded < i64::MAX as f64) {
+        overflow("multiply");
+    }
+    rounded as i64
+}
+
+#[inline]
+fn assert_same_symbol(lhs: &Currency, rhs: &Currency, operation: &str) {
+    if lhs.symbol != rhs.symbol {
+        panic!(
+            "Cannot {} two different types of currency!\n{:?} vs {:?}",
+            operation, lhs.symbol, rhs.symbol
+        );
+    }
+}
+
+/// Arithmetic with explicit overflow handling.
+impl Currency {
+    /// Adds `rhs`, returning `None` on overflow.
+    ///
+    /// # Panics
+    /// Panics if the two currencies have different symbols.
+    pub fn checked_add(self, rhs: Currency) -> Option<Currency> {
+        assert_same_symbol(&self, &rhs, "add");
+        self.map_checked(self.value.checked_add(rhs.value))
+    }
+
+    /// Subtracts `rhs`, returning `None` on overflow.
+    ///
+    /// # Panics
+    /// Panics if the two currencies have different symbols.
+    pub fn checked_sub(self, rhs: Currency) -> Option<Currency> {
+        assert_same_symbol(&self, &rhs, "subtract");
+        self.map_checked(self.value.checked_sub(rhs.value))
+    }
+
+    /// Multiplies by `rhs`, returning `None` on overflow.
+    pub fn checked_mul(self, rhs: i64) -> Option<Currency> {
+        self.map_checked(self.value.checked_mul(rhs))
+    }
+
+    /// Divides by `rhs`, returning `None` if `rhs` is zero or on overflow.
+    pub fn checked_div(self, rhs: i64) -> Option<Currency> {
+        self.map_checked(self.value.checked_div(rhs))
+    }
+
+    /// Negates the amount, returning `None` on overflow.
+    pub fn checked_neg(self) -> Option<Currency> {
+        self.map_checked(self.value.checked_neg())
+    }
+
+    /// Adds `rhs`, clamping the result at the numeric bounds.
+    ///
+    /// # Panics
+    /// Panics if the two currencies have different symbols.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::Currency;
+    ///
+    /// let max = Currency::from(('$', i64::MAX));
+    /// assert_eq!(max.saturating_add(Currency::from(('$', 1))), max);
+    /// ```
+    pub fn saturating_add(self, rhs: Currency) -> Currency {
+        assert_same_symbol(&self, &rhs, "add");
+        self.with_value(self.value.saturating_add(rhs.value))
+    }
+
+    /// Subtracts `rhs`, clamping the result at the numeric bounds.
+    ///
+    /// # Panics
+    /// Panics if the two currencies have different symbols.
+    pub fn saturating_sub(self, rhs: Currency) -> Currency {
+        assert_same_symbol(&self, &rhs, "subtract");
+        self.with_value(self.value.saturating_sub(rhs.value))
+    }
+
+    /// Multiplies by `rhs`, clamping the result at the numeric bounds.
+    pub fn saturating_mul(self, rhs: i64) -> Currency {
+        self.with_value(self.value.saturating_mul(rhs))
+    }
+
+    /// Negates the amount, clamping the result at the numeric bounds.
+    pub fn saturating_neg(self) -> Currency {
+        self.with_value(self.value.saturating_neg())
+    }
+
+    /// Adds `rhs`, wrapping around at the numeric bounds.
+    ///
+    /// # Panics
+    /// Panics if the two currencies have different symbols.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::Currency;
+    ///
+    /// let max = Currency::from(('$', i64::MAX));
+    /// assert_eq!(max.wrapping_add(Currency::from(('$', 1))), Currency::from(('$', i64::MIN)));
+    /// ```
+    pub fn wrapping_add(self, rhs: Currency) -> Currency {
+        assert_same_symbol(&self, &rhs, "add");
+        self.with_value(self.value.wrapping_add(rhs.value))
+    }
+
+    /// Subtracts `rhs`, wrapping around at the numeric bounds.
+    ///
+    /// # Panics
+    /// Panics if the two currencies have different symbols.
+    pub fn wrapping_sub(self, rhs: Currency) -> Currency {
+        assert_same_symbol(&self, &rhs, "subtract");
+        self.with_value(self.value.wrapping_sub(rhs.value))
+    }
+
+    /// Multiplies by `rhs`, wrapping around at the numeric bounds.
+    pub fn wrapping_mul(self, rhs: i64) -> Currency {
+        self.with_value(self.value.wrapping_mul(rhs))
+    }
+
+    /// Negates the amount, wrapping around at the numeric bounds.
+    pub fn wrapping_neg(self) -> Currency {
+        self.with_value(self.value.wrapping_neg())
+    }
+
+    /// Splits the amount into `parts` amounts that differ by at most one coin and sum up to `self`.
+    ///
+    /// The first parts receive the remainder. Zero parts result in an empty vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::Currency;
+    ///
+    /// let parts = Currency::from(('$', 1000)).split(3);
+    /// assert_eq!(parts, vec![Currency::from(('$', 334)), Currency::from(('$', 333)), Currency::from(('$', 333))]);
+    /// ```
+    pub fn split(self, parts: u32) -> Vec<Currency> {
+        if parts == 0 {
+            return Vec::new();
+        }
+        let base = self.value / i64::from(parts);
+        let remainder = (self.value % i64::from(parts)).unsigned_abs();
+        (0..u64::from(parts))
+            .map(|index| {
+                if index < remainder {
+                    self.with_value(base + self.value.signum())
+                } else {
+                    self.with_value(base)
+                }
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn with_value(self, value: i64) -> Currency {
+        Currency {
+            symbol: self.symbol,
+            value,
+        }
+    }
+
+    #[inline]
+    fn map_checked(self, value: Option<i64>) -> Option<Currency> {
+        value.map(|value| self.with_value(value))
+    }
+}
 
 /// Overloads the '+' operator for Currency objects.
 ///
 /// # Panics
 /// Panics if the two addends are different types of currency, as denoted by the Currency's symbol.
+/// Use an [`Amount`] to add a value without a symbol.
 impl Add for Currency {
     type Output = Currency;
 
     #[inline]
     fn add(self, rhs: Currency) -> Currency {
-        if self.symbol == rhs.symbol || self.symbol.is_none() {
-            Currency {
-                symbol: rhs.symbol,
-                value: self.value + rhs.value,
-            }
-        } else {
-            panic!(
-                "Cannot add two different types of currency!\n{:?} vs {:?}",
-                self.symbol, rhs.symbol
-            );
-        }
+        assert_same_symbol(&self, &rhs, "add");
+        self.with_value(add_values(self.value, rhs.value))
     }
 }
 
@@ -30,23 +327,87 @@ impl Add for Currency {
 ///
 /// # Panics
 /// Panics if the minuend and subtrahend are two different types of currency, as denoted by the
-/// Currency's symbol.
+/// Currency's symbol. Use an [`Amount`] to subtract a value without a symbol.
 impl Sub for Currency {
     type Output = Currency;
 
     #[inline]
     fn sub(self, rhs: Currency) -> Currency {
-        if self.symbol == rhs.symbol || self.symbol.is_none() {
-            Currency {
-                symbol: rhs.symbol,
-                value: self.value - rhs.value,
-            }
-        } else {
-            panic!("Cannot subtract two different types of currency!");
+        assert_same_symbol(&self, &rhs, "subtract");
+        self.with_value(sub_values(self.value, rhs.value))
+    }
+}
+
+/// Adds a unit-less amount, keeping the symbol of the currency.
+impl Add<Amount> for Currency {
+    type Output = Currency;
+
+    #[inline]
+    fn add(self, rhs: Amount) -> Currency {
+        Currency {
+            symbol: self.symbol,
+            value: add_values(self.value, rhs.0),
+        }
+    }
+}
+
+/// Subtracts a unit-less amount, keeping the symbol of the currency.
+impl Sub<Amount> for Currency {
+    type Output = Currency;
+
+    #[inline]
+    fn sub(self, rhs: Amount) -> Currency {
+        Currency {
+            symbol: self.symbol,
+            value: sub_values(self.value, rhs.0),
+        }
+    }
+}
+
+/// Adds a currency to a unit-less amount, the result has the symbol of the currency.
+impl Add<Currency> for Amount {
+    type Output = Currency;
+
+    #[inline]
+    fn add(self, rhs: Currency) -> Currency {
+        Currency {
+            symbol: rhs.symbol,
+            value: add_values(self.0, rhs.value),
         }
     }
 }
 
+/// Subtracts a currency from a unit-less amount, the result has the symbol of the currency.
+impl Sub<Currency> for Amount {
+    type Output = Currency;
+
+    #[inline]
+    fn sub(self, rhs: Currency) -> Currency {
+        Currency {
+            symbol: rhs.symbol,
+            value: sub_values(self.0, rhs.value),
+        }
+    }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+
+    #[inline]
+    fn add(self, rhs: Amount) -> Amount {
+        Amount(add_values(self.0, rhs.0))
+    }
+}
+
+impl Sub for Amount {
+    type Output = Amount;
+
+    #[inline]
+    fn sub(self, rhs: Amount) -> Amount {
+        Amount(sub_values(self.0, rhs.0))
+    }
+}
+
 /// Overloads the '*' operator for Currency objects.
 ///
 /// Allows a Currency to be multiplied by an i64.
@@ -57,7 +418,7 @@ impl Mul<i64> for Currency {
     fn mul(self, rhs: i64) -> Currency {
         Currency {
             symbol: self.symbol,
-            value: self.value * rhs,
+            value: mul_values(self.value, rhs),
         }
     }
 }
@@ -73,7 +434,7 @@ impl Mul<Currency> for i64 {
     fn mul(self, rhs: Currency) -> Currency {
         Currency {
             symbol: rhs.symbol,
-            value: rhs.value * self,
+            value: mul_values(rhs.value, self),
         }
     }
 }
@@ -86,7 +447,7 @@ impl Mul<f64> for Currency {
     fn mul(self, rhs: f64) -> Currency {
         Currency {
             symbol: self.symbol,
-            value: (self.value as f64 * rhs).round() as i64,
+            value: float_to_value(self.value as f64 * rhs),
         }
     }
 }
@@ -100,6 +461,30 @@ impl Mul<Currency> for f64 {
     }
 }
 
+/// Multiplies by a decimal quantity exactly, rounding the result half away from zero.
+///
+/// Use [`Currency::mul_quantity`] for other rounding modes.
+///
+/// # Panics
+/// Panics on overflow in every build, like [`Currency::mul_quantity`].
+impl Mul<Quantity> for Currency {
+    type Output = Currency;
+
+    #[inline]
+    fn mul(self, rhs: Quantity) -> Currency {
+        self.mul_quantity(rhs, RoundingMode::HalfUp)
+    }
+}
+
+impl Mul<Currency> for Quantity {
+    type Output = Currency;
+
+    #[inline]
+    fn mul(self, rhs: Currency) -> Currency {
+        rhs * self
+    }
+}
+
 /// Overloads the '/' operator for Currency objects.
 ///
 /// Allows a Currency to be divided by an i64.
@@ -110,13 +495,136 @@ impl Div<i64> for Currency {
     fn div(self, rhs: i64) -> Currency {
         Currency {
             symbol: self.symbol,
-            value: self.value / rhs,
+            value: div_values(self.value, rhs),
+        }
+    }
+}
+
+/// Overloads the '/' operator for two Currency objects.
+///
+/// Returns the ratio of the two amounts as a float, e.g. the share of a budget that is spent.
+/// Use [`Currency::ratio`] for an exact result.
+///
+/// # Panics
+/// Panics if the two currencies have different symbols or the divisor is zero.
+impl Div for Currency {
+    type Output = f64;
+
+    #[inline]
+    fn div(self, rhs: Currency) -> f64 {
+        assert_same_symbol(&self, &rhs, "divide");
+        if rhs.value == 0 {
+            panic!("Cannot divide by a zero amount of currency!");
+        }
+        self.value as f64 / rhs.value as f64
+    }
+}
+
+/// Overloads the '%' operator for Currency objects.
+///
+/// Returns what is left after dividing a Currency by an i64, e.g. the cents that cannot be split
+/// evenly.
+impl Rem<i64> for Currency {
+    type Output = Currency;
+
+    #[inline]
+    fn rem(self, rhs: i64) -> Currency {
+        Currency {
+            symbol: self.symbol,
+            value: rem_values(self.value, rhs),
+        }
+    }
+}
+
+/// Overloads the '%' operator for two Currency objects.
+///
+/// # Panics
+/// Panics if the two currencies have different symbols.
+impl Rem for Currency {
+    type Output = Currency;
+
+    #[inline]
+    fn rem(self, rhs: Currency) -> Currency {
+        assert_same_symbol(&self, &rhs, "take the remainder of");
+        self.with_value(rem_values(self.value, rhs.value))
+    }
+}
+
+/// Overloads the unary '-' operator, e.g. for refunds.
+impl Neg for Currency {
+    type Output = Currency;
+
+    #[inline]
+    fn neg(self) -> Currency {
+        Currency {
+            symbol: self.symbol,
+            value: neg_value(self.value),
         }
     }
 }
 
+impl Neg for &Currency {
+    type Output = Currency;
+
+    #[inline]
+    fn neg(self) -> Currency {
+        -*self
+    }
+}
+
+impl Neg for Amount {
+    type Output = Amount;
+
+    #[inline]
+    fn neg(self) -> Amount {
+        Amount(neg_value(self.0))
+    }
+}
+
+impl Neg for &Amount {
+    type Output = Amount;
+
+    #[inline]
+    fn neg(self) -> Amount {
+        -*self
+    }
+}
+
+forward_ref_binop!(impl Add, add for Currency, Currency);
+forward_ref_binop!(impl Sub, sub for Currency, Currency);
+forward_ref_binop!(impl Add, add for Currency, Amount);
+forward_ref_binop!(impl Sub, sub for Currency, Amount);
+forward_ref_binop!(impl Add, add for Amount, Currency);
+forward_ref_binop!(impl Sub, sub for Amount, Currency);
+forward_ref_binop!(impl Add, add for Amount, Amount);
+forward_ref_binop!(impl Sub, sub for Amount, Amount);
+forward_ref_binop!(impl Mul, mul for Currency, i64);
+forward_ref_binop!(impl Mul, mul for i64, Currency);
+forward_ref_binop!(impl Mul, mul for Currency, f64);
+forward_ref_binop!(impl Mul, mul for f64, Currency);
+forward_ref_binop!(impl Mul, mul for Currency, Quantity);
+forward_ref_binop!(impl Mul, mul for Quantity, Currency);
+forward_ref_binop!(impl Div, div for Currency, i64);
+forward_ref_binop!(impl Div, div for Currency, Currency);
+forward_ref_binop!(impl Rem, rem for Currency, i64);
+forward_ref_binop!(impl Rem, rem for Currency, Currency);
+
+impl_op_assign!(impl AddAssign, add_assign for Currency, Currency, Add, add);
+impl_op_assign!(impl SubAssign, sub_assign for Currency, Currency, Sub, sub);
+impl_op_assign!(impl AddAssign, add_assign for Currency, Amount, Add, add);
+impl_op_assign!(impl SubAssign, sub_assign for Currency, Amount, Sub, sub);
+impl_op_assign!(impl AddAssign, add_assign for Amount, Amount, Add, add);
+impl_op_assign!(impl SubAssign, sub_assign for Amount, Amount, Sub, sub);
+impl_op_assign!(impl MulAssign, mul_assign for Currency, i64, Mul, mul);
+impl_op_assign!(impl MulAssign, mul_assign for Currency, f64, Mul, mul);
+impl_op_assign!(impl MulAssign, mul_assign for Currency, Quantity, Mul, mul);
+impl_op_assign!(impl DivAssign, div_assign for Currency, i64, Div, div);
+impl_op_assign!(impl RemAssign, rem_assign for Currency, i64, Rem, rem);
+impl_op_assign!(impl RemAssign, rem_assign for Currency, Currency, Rem, rem);
+
 #[cfg(test)]
 mod test_arithmetic {
+    use crate::amount::Amount;
     use crate::Currency;
 
     fn dollars(value: i64) -> Currency {
@@ -141,13 +649,50 @@ mod test_arithmetic {
     }
 
     #[test]
-    fn bare_lhs_adopts_rhs_symbol_on_add() {
-        assert_eq!(bare(100) + dollars(50), dollars(150));
+    fn add_bare_to_bare() {
+        assert_eq!(bare(100) + bare(50), bare(150));
+    }
+
+    #[test]
+    fn amount_lhs_adopts_rhs_symbol_on_add() {
+        assert_eq!(Amount(100) + dollars(50), dollars(150));
     }
 
     #[test]
-    fn bare_lhs_adopts_rhs_symbol_on_sub() {
-        assert_eq!(bare(100) - dollars(50), dollars(50));
+    fn amount_lhs_adopts_rhs_symbol_on_sub() {
+        assert_eq!(Amount(100) - dollars(50), dollars(50));
+    }
+
+    #[test]
+    fn amount_rhs_keeps_lhs_symbol() {
+        assert_eq!(dollars(100) + Amount(50), dollars(150));
+        assert_eq!(dollars(100) - Amount(50), dollars(50));
+        assert_eq!(bare(100) + Amount(50), bare(150));
+    }
+
+    #[test]
+    fn amount_plus_amount() {
+        assert_eq!(Amount(100) + Amount(50), Amount(150));
+        assert_eq!(Amount(100) - Amount(50), Amount(50));
+    }
+
+    #[test]
+    fn equality_with_amount_ignores_symbol() {
+        assert_eq!(dollars(100), Amount(100));
+        assert_eq!(Amount(100), bare(100));
+        assert_ne!(dollars(100), bare(100));
+    }
+
+    #[test]
+    #[should_panic]
+    fn bare_lhs_plus_symbolled_rhs_panics() {
+        let _ = bare(100) + dollars(50);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bare_lhs_minus_symbolled_rhs_panics() {
+        let _ = bare(100) - dollars(50);
     }
 
     #[test]
@@ -181,4 +726,153 @@ mod test_arithmetic {
         };
         let _ = dollars(100) - euro;
     }
+
+    #[test]
+    fn neg() {
+        assert_eq!(-dollars(100), dollars(-100));
+        assert_eq!(-&dollars(-100), dollars(100));
+        assert_eq!(-Amount(5), Amount(-5));
+    }
+
+    #[test]
+    fn rem() {
+        assert_eq!(dollars(1001) % 3, dollars(2));
+        assert_eq!(dollars(1001) % dollars(250), dollars(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_symbols_rem_panics() {
+        let _ = dollars(100) % bare(30);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn div_currency_is_ratio() {
+        assert_eq!(dollars(250) / dollars(1000), 0.25);
+        assert_eq!(&dollars(-500) / &dollars(1000), -0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_by_zero_currency_panics() {
+        let _ = dollars(250) / dollars(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_symbols_div_panics() {
+        let _ = dollars(250) / bare(100);
+    }
+
+    #[test]
+    fn assign() {
+        let mut total = dollars(0);
+        for item in &[dollars(100), dollars(250)] {
+            total += item;
+        }
+        assert_eq!(total, dollars(350));
+        total -= dollars(50);
+        assert_eq!(total, dollars(300));
+        total += Amount(1);
+        total -= &Amount(2);
+        assert_eq!(total, dollars(299));
+        total *= 2;
+        assert_eq!(total, dollars(598));
+        total *= 0.5;
+        assert_eq!(total, dollars(299));
+        total /= 2;
+        assert_eq!(total, dollars(149));
+        total %= 100;
+        assert_eq!(total, dollars(49));
+        total %= dollars(20);
+        assert_eq!(total, dollars(9));
+
+        let mut amount = Amount(1);
+        amount += Amount(2);
+        amount -= &Amount(1);
+        assert_eq!(amount, Amount(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_symbols_add_assign_panics() {
+        let mut total = dollars(100);
+        total += bare(1);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn references() {
+        let (a, b) = (dollars(300), dollars(100));
+        assert_eq!(&a + &b, dollars(400));
+        assert_eq!(&a - b, dollars(200));
+        assert_eq!(a + &b, dollars(400));
+        assert_eq!(&a * 2, dollars(600));
+        assert_eq!(2 * &a, dollars(600));
+        assert_eq!(&a * 0.5, dollars(150));
+        assert_eq!(&a / 3, dollars(100));
+        assert_eq!(&a % &b, dollars(0));
+        assert_eq!(&Amount(1) + &a, dollars(301));
+        assert_eq!(&a - &Amount(1), dollars(299));
+    }
+
+    #[test]
+    fn checked() {
+        assert_eq!(dollars(1).checked_add(dollars(2)), Some(dollars(3)));
+        assert_eq!(dollars(i64::MAX).checked_add(dollars(1)), None);
+        assert_eq!(dollars(i64::MIN).checked_sub(dollars(1)), None);
+        assert_eq!(dollars(i64::MAX).checked_mul(2), None);
+        assert_eq!(dollars(10).checked_div(0), None);
+        assert_eq!(dollars(i64::MIN).checked_div(-1), None);
+        assert_eq!(dollars(i64::MIN).checked_neg(), None);
+    }
+
+    #[test]
+    fn saturating() {
+        assert_eq!(
+            dollars(i64::MAX).saturating_add(dollars(1)),
+            dollars(i64::MAX)
+        );
+        assert_eq!(
+            dollars(i64::MIN).saturating_sub(dollars(1)),
+            dollars(i64::MIN)
+        );
+        assert_eq!(dollars(i64::MIN).saturating_mul(2), dollars(i64::MIN));
+        assert_eq!(dollars(i64::MIN).saturating_neg(), dollars(i64::MAX));
+    }
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(
+            dollars(i64::MAX).wrapping_add(dollars(1)),
+            dollars(i64::MIN)
+        );
+        assert_eq!(
+            dollars(i64::MIN).wrapping_sub(dollars(1)),
+            dollars(i64::MAX)
+        );
+        assert_eq!(dollars(i64::MAX).wrapping_mul(2), dollars(-2));
+        assert_eq!(dollars(i64::MIN).wrapping_neg(), dollars(i64::MIN));
+    }
+
+    #[test]
+    #[should_panic]
+    fn saturating_mixed_symbols_panics() {
+        let _ = dollars(1).saturating_add(bare(1));
+    }
+
+    #[test]
+    #[cfg(any(debug_assertions, feature = "overflow-checks"))]
+    #[should_panic]
+    fn operator_overflow_panics() {
+        let _ = dollars(i64::MAX) + dollars(1);
+    }
+
+    #[test]
+    #[cfg(feature = "overflow-checks")]
+    #[should_panic(expected = "Currency overflow")]
+    fn float_overflow_panics_with_feature() {
+        let _ = dollars(i64::MAX) * 2.0;
+    }
 }
@@ -0,0 +1,487 @@
+//! Strict parsing with explicit or inferred separators.
+//!
+//! Unlike [`Currency::from_string`], which treats `.` and `,` interchangeably, the functions in
+//! this module either take the separators from a [`Locale`] or infer them and refuse to guess when
+//! the input is ambiguous.
+//!
+//! # Inference heuristic
+//!
+//! Without a locale the separators are determined like this:
+//!
+//! 1. spaces and apostrophes are always group separators
+//! 2. if both `.` and `,` occur, the one occurring last is the decimal separator
+//! 3. a separator occurring more than once is a group separator
+//! 4. a separator occurring once is the decimal separator, unless it is followed by exactly three
+//!    digits, in which case it could be either and the input is rejected as
+//!    [`ParseError::Ambiguous`] (`"1,234"`). A leading `0` (`"0,125"`) or another group separator
+//!    (`"1 234,567"`) resolves the ambiguity.
+//!
+//! Groups must consistently follow either thousands grouping (`1,234,567`) or Indian grouping
+//! (`12,34,567`).
+//!
+//! # Normalization
+//!
+//! Before parsing, the input is run through [`normalize`], so digits of other scripts, fullwidth
+//! characters and non-breaking spaces are accepted as well.
+//!
+//! # Fractional part
+//!
+//! The fractional part may have any length, but since `Currency` counts in hundredths every digit
+//! after the second must be `0`, otherwise the input is rejected as [`ParseError::TooPrecise`].
+
+use std::error::Error;
+use std::fmt;
+
+use regex::Regex;
+
+use crate::locale::{Grouping, Locale};
+use crate::{Currency, Symbol};
+
+/// Reasons why a string could not be parsed into a `Currency`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input does not look like an amount of money.
+    Invalid,
+    /// The input could be read with either `.` or `,` as the decimal separator.
+    Ambiguous,
+    /// The digit groups of the integer part are not consistent.
+    InvalidGrouping,
+    /// The fractional part has non-zero digits beyond the hundredths.
+    TooPrecise,
+    /// The amount does not fit into an `i64`.
+    Overflow,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ParseError::Invalid => "not a valid amount",
+            ParseError::Ambiguous => "ambiguous decimal separator",
+            ParseError::InvalidGrouping => "inconsistent digit grouping",
+            ParseError::TooPrecise => "more than two significant fractional digits",
+            ParseError::Overflow => "amount out of range",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Zero digits of the scripts whose decimal digits are mapped to ASCII by [`normalize`].
+const DIGIT_ZEROS: &[u32] = &[
+    0x0660, // Arabic-Indic
+    0x06F0, // Extended Arabic-Indic
+    0x07C0, // NKo
+    0x0966, // Devanagari
+    0x09E6, // Bengali
+    0x0A66, // Gurmukhi
+    0x0AE6, // Gujarati
+    0x0B66, // Oriya
+    0x0BE6, // Tamil
+    0x0C66, // Telugu
+    0x0CE6, // Kannada
+    0x0D66, // Malayalam
+    0x0E50, // Thai
+    0x0ED0, // Lao
+    0x0F20, // Tibetan
+    0x1040, // Myanmar
+    0x17E0, // Khmer
+    0x1810, // Mongolian
+    0xFF10, // Fullwidth
+];
+
+/// Maps the input to the ASCII characters the parser understands.
+///
+/// * decimal digits of other scripts (Arabic-Indic, Devanagari, fullwidth, ...) become `0`-`9`
+/// * fullwidth forms (`＄`, `．`, `，`, `－`, `￥`, `￡`) become their regular counterparts
+/// * the Arabic decimal separator `٫` becomes `.`
+/// * non-breaking, narrow and thin spaces as well as the Arabic thousands separator `٬` become
+///   a regular space, which is always read as a group separator
+/// * `’` becomes `'` and the minus sign `−` becomes `-`
+/// * bidirectional control marks are removed
+///
+/// # Examples
+/// ```
+/// use claude::parse::normalize;
+///
+/// assert_eq!(normalize("＄１２．００"), "$12.00");
+/// assert_eq!(normalize("١٢٣٫٤٥"), "123.45");
+/// assert_eq!(normalize("1\u{202F}234,56\u{A0}€"), "1 234,56 €");
+/// ```
+pub fn normalize(s: &str) -> String {
+    s.chars().filter_map(normalize_char).collect()
+}
+
+fn normalize_char(c: char) -> Option<char> {
+    let code = u32::from(c);
+    if let Some(zero) = DIGIT_ZEROS
+        .iter()
+        .find(|&&zero| (zero..zero + 10).contains(&code))
+    {
+        return char::from_digit(code - zero, 10);
+    }
+    let mapped = match c {
+        '\u{FF01}'..='\u{FF5E}' => return char::from_u32(code - 0xFEE0),
+        '\u{FFE0}' => '¢',
+        '\u{FFE1}' => '£',
+        '\u{FFE5}' => '¥',
+        '\u{FFE6}' => '₩',
+        '\u{066B}' => '.',
+        '\u{066C}' | '\u{00A0}' | '\u{2007}' | '\u{2009}' | '\u{202F}' | '\u{3000}' => ' ',
+        '\u{2019}' => '\'',
+        '\u{2212}' => '-',
+        '\u{200E}' | '\u{200F}' | '\u{061C}' => return None,
+        c => c,
+    };
+    Some(mapped)
+}
+
+thread_local! {
+    /// Sign, symbol, digits and separators, with the symbol and sign on either side.
+    static AMOUNT: Regex =
+        Regex::new(r"^(-)?(\p{Sc})?\s*(-)?([0-9](?:[0-9.,' ]*[0-9])?)\s*(\p{Sc})?$").unwrap();
+}
+
+/// Separators used to read the digits of an amount.
+struct Separators {
+    decimal: Option<char>,
+    groups: Vec<char>,
+    grouping: Option<Grouping>,
+}
+
+/// Parses `s` with the separators of the given `locale`.
+///
+/// Spaces are accepted as group separators in every locale.
+pub fn parse_with_locale(s: &str, locale: Locale) -> Result<Currency, ParseError> {
+    parse(s, |_| {
+        Ok(Separators {
+            decimal: Some(locale.decimal_separator()),
+            groups: vec![locale.group_separator(), ' '],
+            grouping: Some(locale.grouping()),
+        })
+    })
+}
+
+/// Parses `s`, inferring the separators as described in the [module documentation](self).
+pub fn parse_inferred(s: &str) -> Result<Currency, ParseError> {
+    parse(s, infer_separators)
+}
+
+fn parse<F>(s: &str, separators: F) -> Result<Currency, ParseError>
+where
+    F: FnOnce(&str) -> Result<Separators, ParseError>,
+{
+    let normalized = normalize(s);
+    AMOUNT.with(|re| parse_captures(re, normalized.trim(), separators))
+}
+
+fn parse_captures<F>(re: &Regex, s: &str, separators: F) -> Result<Currency, ParseError>
+where
+    F: FnOnce(&str) -> Result<Separators, ParseError>,
+{
+    let caps = re.captures(s).ok_or(ParseError::Invalid)?;
+
+    let first_char = |i: usize| caps.get(i).and_then(|m| m.as_str().chars().next());
+    let negative = match (caps.get(1), caps.get(3)) {
+        (Some(_), Some(_)) => return Err(ParseError::Invalid),
+        (leading, trailing) => leading.is_some() || trailing.is_some(),
+    };
+    let symbol: Option<Symbol> = match (first_char(2), first_char(5)) {
+        (Some(_), Some(_)) => return Err(ParseError::Invalid),
+        (prefix, postfix) => prefix.or(postfix),
+    };
+
+    let body = caps.get(4).map_or("", |m| m.as_str());
+    let magnitude = parse_number(body, &separators(body)?)?;
+    let value = if negative { -magnitude } else { magnitude };
+
+    Ok(Currency {
+        symbol,
+        value: i64::try_from(value).map_err(|_| ParseError::Overflow)?,
+    })
+}
+
+fn infer_separators(body: &str) -> Result<Separators, ParseError> {
+    let mut groups = vec![' ', '\''];
+    let last_dot = body.rfind('.');
+    let last_comma = body.rfind(',');
+
+    let decimal = match (last_dot, last_comma) {
+        (None, None) => None,
+        (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+        (Some(_), None) if body.matches('.').count() > 1 => None,
+        (None, Some(_)) if body.matches(',').count() > 1 => None,
+        (Some(pos), None) | (None, Some(pos)) => {
+            let (int, frac) = (&body[..pos], &body[pos + 1..]);
+            let grouped_elsewhere = int.contains(&groups[..]);
+            if frac.len() == 3 && int != "0" && !grouped_elsewhere {
+                return Err(ParseError::Ambiguous);
+            }
+            Some(body[pos..].chars().next().unwrap_or('.'))
+        }
+    };
+
+    groups.extend(['.', ','].iter().filter(|&&c| Some(c) != decimal));
+    Ok(Separators {
+        decimal,
+        groups,
+        grouping: None,
+    })
+}
+
+/// Parses the digits into the magnitude in hundredths, wider than `i64` so that the magnitude of
+/// `i64::MIN` fits.
+fn parse_number(body: &str, separators: &Separators) -> Result<i128, ParseError> {
+    let (int, frac) = match separators.decimal.and_then(|d| body.rfind(d)) {
+        Some(pos) => (&body[..pos], &body[pos + 1..]),
+        None => (body, ""),
+    };
+    if !frac.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ParseError::Invalid);
+    }
+
+    let whole = parse_integer_part(int, separators)?;
+
+    if frac[frac.len().min(2)..].chars().any(|c| c != '0') {
+        return Err(ParseError::TooPrecise);
+    }
+    let cents = match frac.len() {
+        0 => 0,
+        1 => frac.parse::<i128>().map_err(|_| ParseError::Invalid)? * 10,
+        _ => frac[..2].parse::<i128>().map_err(|_| ParseError::Invalid)?,
+    };
+
+    whole
+        .checked_mul(100)
+        .and_then(|v| v.checked_add(cents))
+        .ok_or(ParseError::Overflow)
+}
+
+fn parse_integer_part(int: &str, separators: &Separators) -> Result<i128, ParseError> {
+    if let Some(c) = int
+        .chars()
+        .find(|c| !c.is_ascii_digit() && !separators.groups.contains(c))
+    {
+        return Err(if Some(c) == separators.decimal {
+            ParseError::InvalidGrouping
+        } else {
+            ParseError::Invalid
+        });
+    }
+
+    let mut used = separators.groups.iter().filter(|g| int.contains(**g));
+    let digits = match (used.next(), used.next()) {
+        (None, _) => int.to_owned(),
+        (Some(_), Some(_)) => return Err(ParseError::InvalidGrouping),
+        (Some(&group), None) => {
+            let parts: Vec<&str> = int.split(group).collect();
+            let consistent = match separators.grouping {
+                Some(grouping) => follows(grouping, &parts),
+                None => follows(Grouping::Thousands, &parts) || follows(Grouping::Indian, &parts),
+            };
+            if !consistent {
+                return Err(ParseError::InvalidGrouping);
+            }
+            parts.concat()
+        }
+    };
+
+    digits.parse::<i128>().map_err(|_| ParseError::Overflow)
+}
+
+/// Checks whether the given digit groups, most significant first, follow `grouping`.
+fn follows(grouping: Grouping, groups: &[&str]) -> bool {
+    let (first, rest) = match groups.split_first() {
+        Some(split) => split,
+        None => return false,
+    };
+    if first.is_empty() || first.len() > 3 {
+        return false;
+    }
+    match grouping {
+        Grouping::Thousands => rest.iter().all(|group| group.len() == 3),
+        Grouping::Indian => match rest.split_last() {
+            None => true,
+            Some((last, middle)) => {
+                first.len() <= 2 && last.len() == 3 && middle.iter().all(|group| group.len() == 2)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(symbol: Option<Symbol>, value: i64) -> Currency {
+        Currency { symbol, value }
+    }
+
+    #[test]
+    fn locale_decides_separator() {
+        assert_eq!(parse_with_locale("1,5", Locale::DeDe), Ok(money(None, 150)));
+        assert_eq!(
+            parse_with_locale("1.234", Locale::DeDe),
+            Ok(money(None, 123400))
+        );
+        assert_eq!(
+            parse_with_locale("$1.234", Locale::EnUs),
+            Err(ParseError::TooPrecise)
+        );
+        assert_eq!(
+            parse_with_locale("1'234.50", Locale::DeCh),
+            Ok(money(None, 123450))
+        );
+    }
+
+    #[test]
+    fn extremes() {
+        assert_eq!(
+            parse_inferred("-92233720368547758.08"),
+            Ok(money(None, i64::MIN))
+        );
+        assert_eq!(
+            parse_inferred("$92233720368547758.07"),
+            Ok(money(Some('$'), i64::MAX))
+        );
+        assert_eq!(
+            parse_inferred("92233720368547758.08"),
+            Err(ParseError::Overflow)
+        );
+        assert_eq!(
+            parse_inferred("-92233720368547758.09"),
+            Err(ParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn fractional_lengths() {
+        let en = |s| parse_with_locale(s, Locale::EnUs);
+        assert_eq!(en("12"), Ok(money(None, 1200)));
+        assert_eq!(en("12.5"), Ok(money(None, 1250)));
+        assert_eq!(en("12.50"), Ok(money(None, 1250)));
+        assert_eq!(en("12.500"), Ok(money(None, 1250)));
+        assert_eq!(en("12.5000000"), Ok(money(None, 1250)));
+        assert_eq!(en("12.501"), Err(ParseError::TooPrecise));
+    }
+
+    #[test]
+    fn grouping_is_validated() {
+        assert_eq!(
+            parse_with_locale("1,23,4.00", Locale::EnUs),
+            Err(ParseError::InvalidGrouping)
+        );
+        assert_eq!(
+            parse_with_locale("1,23,456.00", Locale::EnUs),
+            Err(ParseError::InvalidGrouping)
+        );
+        assert_eq!(
+            parse_with_locale("1,23,456.00", Locale::EnIn),
+            Ok(money(None, 12345600))
+        );
+        assert_eq!(
+            parse_with_locale("1,234,567.89", Locale::EnUs),
+            Ok(money(None, 123456789))
+        );
+        assert_eq!(
+            parse_with_locale("1,234 567.89", Locale::EnUs),
+            Err(ParseError::InvalidGrouping)
+        );
+    }
+
+    #[test]
+    fn inferred() {
+        assert_eq!(parse_inferred("1,5"), Ok(money(None, 150)));
+        assert_eq!(parse_inferred("€12.000,99"), Ok(money(Some('€'), 1200099)));
+        assert_eq!(
+            parse_inferred("-$12,000.99"),
+            Ok(money(Some('$'), -1200099))
+        );
+        assert_eq!(parse_inferred("1.234.567"), Ok(money(None, 123456700)));
+        assert_eq!(parse_inferred("0,125"), Err(ParseError::TooPrecise));
+        assert_eq!(parse_inferred("0,120"), Ok(money(None, 12)));
+        assert_eq!(parse_inferred("1 234,5"), Ok(money(None, 123450)));
+        assert_eq!(parse_inferred("12,10€"), Ok(money(Some('€'), 1210)));
+        assert_eq!(parse_inferred("1.234"), Err(ParseError::Ambiguous));
+        assert_eq!(parse_inferred("1,234"), Err(ParseError::Ambiguous));
+        assert_eq!(parse_inferred("1,2,3"), Err(ParseError::InvalidGrouping));
+        assert_eq!(
+            parse_inferred("1.234,56.7"),
+            Err(ParseError::InvalidGrouping)
+        );
+    }
+
+    #[test]
+    fn latin() {
+        assert_eq!(
+            parse_with_locale("1\u{00A0}234,56\u{00A0}€", Locale::DeDe),
+            Ok(money(Some('€'), 123456))
+        );
+        assert_eq!(parse_inferred("1\u{202F}234.56"), Ok(money(None, 123456)));
+        assert_eq!(
+            parse_inferred("1\u{2009}234\u{2009}567"),
+            Ok(money(None, 123456700))
+        );
+        assert_eq!(
+            parse_with_locale("1’234.50", Locale::DeCh),
+            Ok(money(None, 123450))
+        );
+        assert_eq!(parse_inferred("\u{2212}$5"), Ok(money(Some('$'), -500)));
+    }
+
+    #[test]
+    fn arabic_indic() {
+        assert_eq!(parse_inferred("١٢٣"), Ok(money(None, 12300)));
+        assert_eq!(parse_inferred("١٬٢٣٤٫٥٠"), Ok(money(None, 123450)));
+        assert_eq!(
+            parse_inferred("\u{200F}٥٠٫٠٠ $"),
+            Ok(money(Some('$'), 5000))
+        );
+        assert_eq!(parse_inferred("۱۲۳٫۴۵"), Ok(money(None, 12345)));
+    }
+
+    #[test]
+    fn devanagari() {
+        assert_eq!(
+            parse_with_locale("₹१,२३,४५६.७८", Locale::EnIn),
+            Ok(money(Some('₹'), 12345678))
+        );
+        assert_eq!(parse_inferred("१२.५"), Ok(money(None, 1250)));
+    }
+
+    #[test]
+    fn fullwidth() {
+        assert_eq!(parse_inferred("＄１２．００"), Ok(money(Some('$'), 1200)));
+        assert_eq!(
+            parse_inferred("￥１，２３４，５６７"),
+            Ok(money(Some('¥'), 123456700))
+        );
+        assert_eq!(parse_inferred("－￡３"), Ok(money(Some('£'), -300)));
+        assert_eq!(
+            Currency::from_string("＄１２．００"),
+            parse_inferred("$12.00").ok()
+        );
+    }
+
+    #[test]
+    fn from_string_normalizes() {
+        assert_eq!(
+            Currency::from_string("€1\u{00A0}234,56"),
+            Some(money(Some('€'), 123456))
+        );
+        assert_eq!(Currency::from_string("٥٫٠٠"), Some(money(None, 500)));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(parse_inferred(""), Err(ParseError::Invalid));
+        assert_eq!(parse_inferred("$"), Err(ParseError::Invalid));
+        assert_eq!(parse_inferred("$12$"), Err(ParseError::Invalid));
+        assert_eq!(parse_inferred("--12"), Err(ParseError::Invalid));
+        assert_eq!(parse_inferred("12."), Err(ParseError::Invalid));
+        assert_eq!(
+            parse_inferred("99999999999999999999"),
+            Err(ParseError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,563 @@
+//! Discounts, coupons and volume pricing.
+//!
+//! A [`Pricing`] is a list of [`Rule`]s applied to a [`LineItem`] or a basket of them, producing
+//! an itemised [`Breakdown`] of every adjustment.
+//!
+//! Rules are applied in a fixed order, regardless of the order they were added in:
+//!
+//! 1. [`Rule::Tiered`] picks the unit price by quantity
+//! 2. [`Rule::BuyXGetY`] makes units free
+//! 3. [`Rule::PercentOff`]
+//! 4. [`Rule::AmountOff`]
+//! 5. [`Rule::Cap`] limits the total discount
+//! 6. [`Rule::Floor`] enforces a minimum price
+//!
+//! Rules of the same kind are applied in the order they were added. Percentages are the only
+//! step that needs rounding, it uses the mode given to [`Pricing::new`]. No step takes the price
+//! below zero, negative unit prices are rejected with [`PricingError::NegativePrice`].
+//!
+//! For a basket the quantity rules (1 and 2) are applied to every line, the remaining rules to
+//! the basket total, so `AmountOff` acts as a coupon for the whole order.
+//!
+//! # Examples
+//! ```
+//! use claude::pricing::{LineItem, Pricing, Rule};
+//! use claude::rounding::RoundingMode;
+//! use claude::Currency;
+//!
+//! let euros = |value| Currency::from(('€', value));
+//! let pricing = Pricing::new(RoundingMode::HalfUp)
+//!     .rule(Rule::AmountOff(euros(500)))
+//!     .rule(Rule::PercentOff("10".parse().unwrap()))
+//!     .rule(Rule::BuyXGetY { buy: 2, free: 1 });
+//!
+//! let breakdown = pricing.price_line(&LineItem::new(euros(1999), 3)).unwrap();
+//! assert_eq!(breakdown.subtotal, euros(5997));
+//! let steps: Vec<_> = breakdown.adjustments.iter().map(|a| a.amount).collect();
+//! assert_eq!(steps, vec![euros(-1999), euros(-400), euros(-500)]);
+//! assert_eq!(breakdown.total, euros(3098));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::decimal::{pow10, Decimal};
+use crate::rounding::{div_round, RoundingMode};
+use crate::{Currency, Symbol};
+
+/// Reasons why a price cannot be calculated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PricingError {
+    /// Prices, rules or lines use different symbols.
+    CurrencyMismatch,
+    /// A unit price or tier price is negative.
+    NegativePrice,
+    /// An amount does not fit.
+    Overflow,
+}
+
+impl fmt::Display for PricingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            PricingError::CurrencyMismatch => "prices and rules must share a currency",
+            PricingError::NegativePrice => "unit prices must not be negative",
+            PricingError::Overflow => "amount out of range",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for PricingError {}
+
+/// The unit price of a volume tier.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Tier {
+    /// Smallest quantity this tier applies to
+    pub min_quantity: u32,
+    /// Price of every unit once the tier applies
+    pub unit_price: Currency,
+}
+
+/// A pricing rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Takes a percentage off, `10` means 10%.
+    PercentOff(Decimal),
+    /// Takes a fixed amount off.
+    AmountOff(Currency),
+    /// Out of every `buy + free` units, `free` units cost nothing.
+    BuyXGetY {
+        /// Units to pay for
+        buy: u32,
+        /// Units given away
+        free: u32,
+    },
+    /// Volume pricing, every unit costs the price of the highest tier the quantity reaches.
+    Tiered(Vec<Tier>),
+    /// The price does not drop below this amount, unless it was lower before any discount.
+    Floor(Currency),
+    /// The total discount does not exceed this amount.
+    Cap(Currency),
+}
+
+impl Rule {
+    fn phase(&self) -> u8 {
+        match self {
+            Rule::Tiered(_) => 0,
+            Rule::BuyXGetY { .. } => 1,
+            Rule::PercentOff(_) => 2,
+            Rule::AmountOff(_) => 3,
+            Rule::Cap(_) => 4,
+            Rule::Floor(_) => 5,
+        }
+    }
+
+    fn is_quantity_rule(&self) -> bool {
+        self.phase() < 2
+    }
+
+    fn amounts(&self) -> Vec<Currency> {
+        match self {
+            Rule::AmountOff(money) | Rule::Floor(money) | Rule::Cap(money) => vec![*money],
+            Rule::Tiered(tiers) => tiers.iter().map(|tier| tier.unit_price).collect(),
+            Rule::PercentOff(_) | Rule::BuyXGetY { .. } => Vec::new(),
+        }
+    }
+}
+
+/// A quantity of one product.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LineItem {
+    /// Price of a single unit
+    pub unit_price: Currency,
+    /// Number of units
+    pub quantity: u32,
+}
+
+impl LineItem {
+    /// Creates a line of `quantity` units at `unit_price`.
+    pub fn new(unit_price: Currency, quantity: u32) -> LineItem {
+        LineItem {
+            unit_price,
+            quantity,
+        }
+    }
+}
+
+/// The change a single rule made to the price.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Adjustment {
+    /// The rule that was applied
+    pub rule: Rule,
+    /// Change of the price, negative for discounts
+    pub amount: Currency,
+}
+
+/// Itemised price of a line or a basket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Breakdown {
+    /// Price before any rule
+    pub subtotal: Currency,
+    /// Every rule that changed the price, in the order they were applied
+    pub adjustments: Vec<Adjustment>,
+    /// Price after all rules
+    pub total: Currency,
+}
+
+/// Itemised price of a basket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasketBreakdown {
+    /// Every line with the quantity rules applied
+    pub lines: Vec<Breakdown>,
+    /// The sum of the line totals with the remaining rules applied
+    pub basket: Breakdown,
+}
+
+/// Running state while applying rules.
+struct Running {
+    symbol: Option<Symbol>,
+    subtotal: i128,
+    total: i128,
+    adjustments: Vec<Adjustment>,
+}
+
+impl Running {
+    fn set_total(&mut self, rule: &Rule, total: i128) -> Result<(), PricingError> {
+        let total = total.max(0);
+        if total != self.total {
+            let amount = self.currency(total - self.total)?;
+            self.adjustments.push(Adjustment {
+                rule: rule.clone(),
+                amount,
+            });
+            self.total = total;
+        }
+        Ok(())
+    }
+
+    fn currency(&self, value: i128) -> Result<Currency, PricingError> {
+        if value > i128::from(i64::MAX) || value < i128::from(i64::MIN) {
+            return Err(PricingError::Overflow);
+        }
+        Ok(Currency {
+            symbol: self.symbol,
+            value: value as i64,
+        })
+    }
+
+    fn finish(self) -> Result<Breakdown, PricingError> {
+        Ok(Breakdown {
+            subtotal: self.currency(self.subtotal)?,
+            total: self.currency(self.total)?,
+            adjustments: self.adjustments,
+        })
+    }
+}
+
+/// An ordered set of pricing rules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pricing {
+    rules: Vec<Rule>,
+    rounding: RoundingMode,
+}
+
+impl Pricing {
+    /// Creates a pricing without rules, percentages are rounded with `rounding`.
+    pub fn new(rounding: RoundingMode) -> Pricing {
+        Pricing {
+            rules: Vec::new(),
+            rounding,
+        }
+    }
+
+    /// Adds a rule.
+    pub fn rule(mut self, rule: Rule) -> Pricing {
+        self.rules.push(rule);
+        self
+    }
+
+    /// The rules in the order they are applied.
+    pub fn rules(&self) -> Vec<&Rule> {
+        let mut rules: Vec<&Rule> = self.rules.iter().collect();
+        rules.sort_by_key(|rule| rule.phase());
+        rules
+    }
+
+    /// Applies all rules to a single line.
+    pub fn price_line(&self, line: &LineItem) -> Result<Breakdown, PricingError> {
+        self.check_symbol(line.unit_price.symbol)?;
+        let mut running = self.apply_quantity_rules(line)?;
+        self.apply_amount_rules(&mut running)?;
+        running.finish()
+    }
+
+    /// Applies the quantity rules to every line and the remaining rules to the basket total.
+    ///
+    /// All lines have to share one symbol.
+    pub fn price_basket(&self, lines: &[LineItem]) -> Result<BasketBreakdown, PricingError> {
+        let symbol = lines.first().and_then(|line| line.unit_price.symbol);
+        if lines.iter().any(|line| line.unit_price.symbol != symbol) {
+            return Err(PricingError::CurrencyMismatch);
+        }
+        // an empty basket has no currency the rules could mismatch
+        if !lines.is_empty() {
+            self.check_symbol(symbol)?;
+        }
+
+        let mut basket = Running {
+            symbol,
+            subtotal: 0,
+            total: 0,
+            adjustments: Vec::new(),
+        };
+        let mut priced = Vec::with_capacity(lines.len());
+        for line in lines {
+            let running = self.apply_quantity_rules(line)?;
+            basket.subtotal += running.subtotal;
+            basket.total += running.total;
+            priced.push(running.finish()?);
+        }
+        self.apply_amount_rules(&mut basket)?;
+        Ok(BasketBreakdown {
+            lines: priced,
+            basket: basket.finish()?,
+        })
+    }
+
+    fn check_symbol(&self, symbol: Option<Symbol>) -> Result<(), PricingError> {
+        let mismatch = self
+            .rules
+            .iter()
+            .flat_map(Rule::amounts)
+            .any(|money| money.symbol != symbol);
+        if mismatch {
+            Err(PricingError::CurrencyMismatch)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn apply_quantity_rules(&self, line: &LineItem) -> Result<Running, PricingError> {
+        let negative_tier = self.rules.iter().any(|rule| match rule {
+            Rule::Tiered(tiers) => tiers.iter().any(|tier| tier.unit_price.value < 0),
+            _ => false,
+        });
+        if line.unit_price.value < 0 || negative_tier {
+            return Err(PricingError::NegativePrice);
+        }
+        let quantity = i128::from(line.quantity);
+        let mut unit = i128::from(line.unit_price.value);
+        let mut running = Running {
+            symbol: line.unit_price.symbol,
+            subtotal: unit * quantity,
+            total: unit * quantity,
+            adjustments: Vec::new(),
+        };
+
+        for rule in self
+            .rules()
+            .into_iter()
+            .filter(|rule| rule.is_quantity_rule())
+        {
+            match *rule {
+                Rule::Tiered(ref tiers) => {
+                    let tier = tiers
+                        .iter()
+                        .filter(|tier| tier.min_quantity <= line.quantity)
+                        .max_by_key(|tier| tier.min_quantity);
+                    if let Some(tier) = tier {
+                        let price = i128::from(tier.unit_price.value);
+                        running.set_total(rule, running.total + (price - unit) * quantity)?;
+                        unit = price;
+                    }
+                }
+                Rule::BuyXGetY { buy, free } => {
+                    let group = u64::from(buy) + u64::from(free);
+                    if let Some(groups) = u64::from(line.quantity).checked_div(group) {
+                        let free_units = groups * u64::from(free);
+                        let discount = unit * i128::from(free_units);
+                        running.set_total(rule, running.total - discount)?;
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+        Ok(running)
+    }
+
+    fn apply_amount_rules(&self, running: &mut Running) -> Result<(), PricingError> {
+        for rule in self
+            .rules()
+            .into_iter()
+            .filter(|rule| !rule.is_quantity_rule())
+        {
+            let total = match *rule {
+                Rule::PercentOff(percent) => {
+                    let numerator = running
+                        .total
+                        .checked_mul(percent.mantissa())
+                        .ok_or(PricingError::Overflow)?;
+                    let discount =
+                        div_round(numerator, 100 * pow10(percent.scale()), self.rounding);
+                    running.total - discount
+                }
+                Rule::AmountOff(money) => running.total - i128::from(money.value),
+                Rule::Cap(money) => running
+                    .total
+                    .max(running.subtotal - i128::from(money.value)),
+                Rule::Floor(money) => {
+                    let floor = i128::from(money.value).min(running.subtotal);
+                    running.total.max(floor)
+                }
+                _ => unreachable!(),
+            };
+            running.set_total(rule, total)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn euros(value: i64) -> Currency {
+        Currency::from(('€', value))
+    }
+
+    fn percent(s: &str) -> Rule {
+        Rule::PercentOff(s.parse().unwrap())
+    }
+
+    fn pricing() -> Pricing {
+        Pricing::new(RoundingMode::HalfUp)
+    }
+
+    #[test]
+    fn order_is_fixed() {
+        let a = pricing()
+            .rule(Rule::AmountOff(euros(100)))
+            .rule(percent("50"));
+        let b = pricing()
+            .rule(percent("50"))
+            .rule(Rule::AmountOff(euros(100)));
+        let line = LineItem::new(euros(1000), 1);
+        assert_eq!(a.price_line(&line).unwrap().total, euros(400));
+        assert_eq!(a.price_line(&line), b.price_line(&line));
+    }
+
+    #[test]
+    fn percent_rounds_explicitly() {
+        let line = LineItem::new(euros(999), 1);
+        let half_up = pricing().rule(percent("12.5"));
+        assert_eq!(half_up.price_line(&line).unwrap().total, euros(874));
+        let down = Pricing::new(RoundingMode::Down).rule(percent("12.5"));
+        assert_eq!(down.price_line(&line).unwrap().total, euros(875));
+    }
+
+    #[test]
+    fn never_below_zero() {
+        let rules = pricing()
+            .rule(Rule::AmountOff(euros(5000)))
+            .rule(percent("150"));
+        let breakdown = rules.price_line(&LineItem::new(euros(1000), 2)).unwrap();
+        assert_eq!(breakdown.total, euros(0));
+        assert_eq!(breakdown.adjustments.len(), 1);
+        assert_eq!(breakdown.adjustments[0].amount, euros(-2000));
+    }
+
+    #[test]
+    fn tiers_and_free_units() {
+        let tiers = Rule::Tiered(vec![
+            Tier {
+                min_quantity: 10,
+                unit_price: euros(800),
+            },
+            Tier {
+                min_quantity: 50,
+                unit_price: euros(600),
+            },
+        ]);
+        let rules = pricing()
+            .rule(Rule::BuyXGetY { buy: 9, free: 1 })
+            .rule(tiers);
+        assert_eq!(
+            rules
+                .price_line(&LineItem::new(euros(1000), 5))
+                .unwrap()
+                .total,
+            euros(5000)
+        );
+        // 20 units at the 10+ tier, two of them free
+        let breakdown = rules.price_line(&LineItem::new(euros(1000), 20)).unwrap();
+        let steps: Vec<_> = breakdown.adjustments.iter().map(|a| a.amount).collect();
+        assert_eq!(steps, vec![euros(-4000), euros(-1600)]);
+        assert_eq!(breakdown.total, euros(14400));
+        assert_eq!(
+            rules
+                .price_line(&LineItem::new(euros(1000), 50))
+                .unwrap()
+                .total,
+            euros(27000)
+        );
+    }
+
+    #[test]
+    fn cap_and_floor() {
+        let capped = pricing().rule(percent("50")).rule(Rule::Cap(euros(300)));
+        let breakdown = capped.price_line(&LineItem::new(euros(1000), 1)).unwrap();
+        assert_eq!(breakdown.total, euros(700));
+        assert_eq!(breakdown.adjustments[1].amount, euros(200));
+
+        let floored = pricing()
+            .rule(Rule::AmountOff(euros(900)))
+            .rule(Rule::Floor(euros(250)));
+        assert_eq!(
+            floored
+                .price_line(&LineItem::new(euros(1000), 1))
+                .unwrap()
+                .total,
+            euros(250)
+        );
+        // a floor never raises the price above the undiscounted price
+        assert_eq!(
+            floored
+                .price_line(&LineItem::new(euros(200), 1))
+                .unwrap()
+                .total,
+            euros(200)
+        );
+        assert_eq!(
+            pricing()
+                .rule(Rule::Floor(euros(250)))
+                .price_line(&LineItem::new(euros(200), 1))
+                .unwrap()
+                .total,
+            euros(200)
+        );
+    }
+
+    #[test]
+    fn basket() {
+        let rules = pricing()
+            .rule(Rule::BuyXGetY { buy: 1, free: 1 })
+            .rule(Rule::AmountOff(euros(500)));
+        let basket = rules
+            .price_basket(&[LineItem::new(euros(1000), 2), LineItem::new(euros(250), 4)])
+            .unwrap();
+        assert_eq!(basket.lines[0].total, euros(1000));
+        assert_eq!(basket.lines[1].total, euros(500));
+        assert_eq!(basket.basket.subtotal, euros(3000));
+        assert_eq!(basket.basket.adjustments.len(), 1);
+        assert_eq!(basket.basket.total, euros(1000));
+    }
+
+    #[test]
+    fn currencies_must_match() {
+        let rules = pricing().rule(Rule::AmountOff(euros(500)));
+        assert_eq!(
+            rules.price_line(&LineItem::new(Currency::from(('$', 1000)), 1)),
+            Err(PricingError::CurrencyMismatch)
+        );
+        assert_eq!(
+            pricing().price_basket(&[
+                LineItem::new(euros(1000), 1),
+                LineItem::new(Currency::from(('$', 1000)), 1)
+            ]),
+            Err(PricingError::CurrencyMismatch)
+        );
+    }
+
+    #[test]
+    fn empty_basket() {
+        let rules = pricing()
+            .rule(Rule::AmountOff(euros(500)))
+            .rule(Rule::Floor(euros(100)))
+            .rule(percent("10"));
+        let breakdown = rules.price_basket(&[]).unwrap();
+        assert!(breakdown.lines.is_empty());
+        assert!(breakdown.basket.adjustments.is_empty());
+        assert_eq!(breakdown.basket.subtotal, Currency::from(0));
+        assert_eq!(breakdown.basket.total, Currency::from(0));
+    }
+
+    #[test]
+    fn negative_prices() {
+        assert_eq!(
+            pricing().price_line(&LineItem::new(euros(-1000), 2)),
+            Err(PricingError::NegativePrice)
+        );
+        assert_eq!(
+            pricing().price_basket(&[LineItem::new(euros(1000), 1), LineItem::new(euros(-1), 1)]),
+            Err(PricingError::NegativePrice)
+        );
+        let tiers = pricing().rule(Rule::Tiered(vec![Tier {
+            min_quantity: 10,
+            unit_price: euros(-100),
+        }]));
+        assert_eq!(
+            tiers.price_line(&LineItem::new(euros(1000), 1)),
+            Err(PricingError::NegativePrice)
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Conversion to and from the protobuf message `google.type.Money`, with the `protobuf` feature.
+//!
+//! [`Money`] has the same fields and tags as the message in `google/type/money.proto`, so it is
+//! wire compatible with the code generated from it.
+//!
+//! # Examples
+//! ```
+//! use std::convert::TryFrom;
+//! use claude::{proto::Money, Currency};
+//!
+//! let money = Money::try_from(Currency::from(('€', -1234))).unwrap();
+//! assert_eq!(money.currency_code, "EUR");
+//! assert_eq!((money.units, money.nanos), (-12, -340_000_000));
+//! assert_eq!(Currency::try_from(money), Ok(Currency::from(('€', -1234))));
+//! ```
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use crate::info::CurrencyInfo;
+use crate::Currency;
+
+/// Nanos per smallest unit.
+const NANOS_PER_UNIT: i32 = 10_000_000;
+/// Nanos per whole unit.
+const NANOS_PER_WHOLE: i32 = 1_000_000_000;
+
+/// Reasons why an amount cannot be converted to or from [`Money`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProtoError {
+    /// The symbol has no ISO 4217 code, or the code has no symbol.
+    UnknownCurrency,
+    /// `nanos` is out of range or its sign differs from `units`.
+    InvalidNanos,
+    /// `nanos` is not a multiple of the smallest unit, converting would lose precision.
+    PrecisionLoss,
+    /// The amount does not fit.
+    Overflow,
+}
+
+impl fmt::Display for ProtoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ProtoError::UnknownCurrency => "currency has no ISO 4217 code and symbol",
+            ProtoError::InvalidNanos => "nanos out of range or with the wrong sign",
+            ProtoError::PrecisionLoss => "nanos are not a multiple of the smallest unit",
+            ProtoError::Overflow => "amount does not fit",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for ProtoError {}
+
+/// An amount of money with its currency, `google.type.Money`.
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct Money {
+    /// The three-letter currency code defined in ISO 4217, empty for amounts without a symbol.
+    #[prost(string, tag = "1")]
+    pub currency_code: String,
+    /// The whole units of the amount.
+    #[prost(int64, tag = "2")]
+    pub units: i64,
+    /// Number of nano (10^-9) units of the amount, between -999,999,999 and +999,999,999 with the
+    /// sign of `units` if that is not zero.
+    #[prost(int32, tag = "3")]
+    pub nanos: i32,
+}
+
+/// Converts losslessly, amounts without a symbol get an empty `currency_code`.
+impl TryFrom<Currency> for Money {
+    type Error = ProtoError;
+
+    fn try_from(money: Currency) -> Result<Money, ProtoError> {
+        let currency_code = match money.symbol {
+            Some(_) => money.info().ok_or(ProtoError::UnknownCurrency)?.code(),
+            None => "",
+        };
+        Ok(Money {
+            currency_code: currency_code.to_string(),
+            units: money.value / 100,
+            nanos: (money.value % 100) as i32 * NANOS_PER_UNIT,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a Money> for Currency {
+    type Error = ProtoError;
+
+    fn try_from(money: &'a Money) -> Result<Currency, ProtoError> {
+        let symbol = if money.currency_code.is_empty() {
+            None
+        } else {
+            let info =
+                CurrencyInfo::from_code(&money.currency_code).ok_or(ProtoError::UnknownCurrency)?;
+            Some(info.symbol().ok_or(ProtoError::UnknownCurrency)?)
+        };
+        let nanos = money.nanos;
+        if nanos <= -NANOS_PER_WHOLE
+            || nanos >= NANOS_PER_WHOLE
+            || (money.units > 0 && nanos < 0)
+            || (money.units < 0 && nanos > 0)
+        {
+            return Err(ProtoError::InvalidNanos);
+        }
+        if nanos % NANOS_PER_UNIT != 0 {
+            return Err(ProtoError::PrecisionLoss);
+        }
+        let value = money
+            .units
+            .checked_mul(100)
+            .and_then(|value| value.checked_add(i64::from(nanos / NANOS_PER_UNIT)))
+            .ok_or(ProtoError::Overflow)?;
+        Ok(Currency { symbol, value })
+    }
+}
+
+impl TryFrom<Money> for Currency {
+    type Error = ProtoError;
+
+    fn try_from(money: Money) -> Result<Currency, ProtoError> {
+        Currency::try_from(&money)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    fn money(code: &str, units: i64, nanos: i32) -> Money {
+        Money {
+            currency_code: code.to_string(),
+            units,
+            nanos,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for &value in &[0, 1, -1, 99, -99, 100, 123_456, i64::MAX, i64::MIN] {
+            for &symbol in &[None, Some('$'), Some('¥')] {
+                let amount = Currency { symbol, value };
+                let converted = Money::try_from(amount).unwrap();
+                assert_eq!(Currency::try_from(&converted), Ok(amount));
+            }
+        }
+        assert_eq!(
+            Money::try_from(Currency::from(('$', 150))),
+            Ok(money("USD", 1, 500_000_000))
+        );
+    }
+
+    #[test]
+    fn wire_format() {
+        let encoded = Money::try_from(Currency::from(('£', 250)))
+            .unwrap()
+            .encode_to_vec();
+        // currency_code, units and nanos with tags 1 to 3
+        assert_eq!(&encoded[..5], b"\x0a\x03GBP");
+        assert_eq!(
+            Currency::try_from(Money::decode(&encoded[..]).unwrap()),
+            Ok(Currency::from(('£', 250)))
+        );
+    }
+
+    #[test]
+    fn precision_loss() {
+        assert_eq!(
+            Currency::try_from(money("EUR", 1, 5)),
+            Err(ProtoError::PrecisionLoss)
+        );
+        assert_eq!(
+            Currency::try_from(money("EUR", 0, -750_000_000)),
+            Ok(Currency::from(('€', -75)))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            Currency::try_from(money("EUR", 1, -10_000_000)),
+            Err(ProtoError::InvalidNanos)
+        );
+        assert_eq!(
+            Currency::try_from(money("EUR", 0, 1_000_000_000)),
+            Err(ProtoError::InvalidNanos)
+        );
+        assert_eq!(
+            Currency::try_from(money("EUR", i64::MAX, 0)),
+            Err(ProtoError::Overflow)
+        );
+        assert_eq!(
+            Currency::try_from(money("XYZ", 1, 0)),
+            Err(ProtoError::UnknownCurrency)
+        );
+        // the crate has no symbol for Swiss francs
+        assert_eq!(
+            Currency::try_from(money("CHF", 1, 0)),
+            Err(ProtoError::UnknownCurrency)
+        );
+        assert_eq!(
+            Money::try_from(Currency::from(('₿', 1))),
+            Err(ProtoError::UnknownCurrency)
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Exact non-integer quantities like `2.5` kg or `0.333` hours.
+//!
+//! Multiplying a [`Currency`] by a [`Quantity`] computes the exact product and rounds once to the
+//! smallest unit, half away from zero with the `*` operator or with any mode through
+//! [`Currency::mul_quantity`].
+//!
+//! # Examples
+//! ```
+//! use claude::quantity::Quantity;
+//! use claude::rounding::RoundingMode;
+//! use claude::Currency;
+//!
+//! let per_hour = Currency::from(('€', 9000));
+//! let hours: Quantity = "0.333".parse().unwrap();
+//! assert_eq!(per_hour * hours, Currency::from(('€', 2997)));
+//!
+//! let per_kg = Currency::from(('€', 399));
+//! let kg: Quantity = "2.5".parse().unwrap();
+//! assert_eq!(per_kg * kg, Currency::from(('€', 998)));
+//! assert_eq!(per_kg.mul_quantity(kg, RoundingMode::HalfEven), Currency::from(('€', 998)));
+//! assert_eq!(per_kg.mul_quantity(kg, RoundingMode::Down), Currency::from(('€', 997)));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::decimal::{Decimal, ParseDecimalError};
+use crate::rounding::RoundingMode;
+use crate::unit_price::UnitPrice;
+use crate::Currency;
+
+/// An exact decimal quantity, e.g. `2.5` or `0.333`.
+///
+/// Quantities compare by value, `1.50 == 1.5`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quantity {
+    value: Decimal,
+}
+
+impl Quantity {
+    /// Creates a quantity of `value`.
+    pub fn new(value: Decimal) -> Quantity {
+        Quantity { value }
+    }
+
+    /// The quantity as a decimal number.
+    pub fn value(&self) -> Decimal {
+        self.value
+    }
+}
+
+impl From<Decimal> for Quantity {
+    fn from(value: Decimal) -> Quantity {
+        Quantity { value }
+    }
+}
+
+impl From<i64> for Quantity {
+    fn from(value: i64) -> Quantity {
+        Quantity {
+            value: Decimal::from(value),
+        }
+    }
+}
+
+impl From<Quantity> for Decimal {
+    fn from(quantity: Quantity) -> Decimal {
+        quantity.value
+    }
+}
+
+/// Parses plain decimal numbers like [`Decimal`], e.g. `2.5` or `-0.333`.
+impl FromStr for Quantity {
+    type Err = ParseDecimalError;
+
+    fn from_str(s: &str) -> Result<Quantity, ParseDecimalError> {
+        s.parse().map(Quantity::new)
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+/// Multiplication by quantities.
+impl Currency {
+    /// Multiplies by `quantity` exactly and rounds once to the smallest unit with `mode`.
+    ///
+    /// # Panics
+    /// Panics if the result does not fit, use [`Currency::checked_mul_quantity`] to handle that.
+    pub fn mul_quantity(self, quantity: Quantity, mode: RoundingMode) -> Currency {
+        self.checked_mul_quantity(quantity, mode)
+            .unwrap_or_else(|| panic!("Currency overflow: attempt to multiply with overflow"))
+    }
+
+    /// Multiplies by `quantity` exactly and rounds once to the smallest unit with `mode`.
+    ///
+    /// Returns `None` if the result does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::quantity::Quantity;
+    /// use claude::rounding::RoundingMode;
+    /// use claude::Currency;
+    ///
+    /// let money = Currency::from(('$', i64::MAX));
+    /// assert_eq!(money.checked_mul_quantity(Quantity::from(2), RoundingMode::HalfUp), None);
+    /// ```
+    pub fn checked_mul_quantity(self, quantity: Quantity, mode: RoundingMode) -> Option<Currency> {
+        UnitPrice::from(self).total(quantity.value, mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qty(s: &str) -> Quantity {
+        s.parse().unwrap()
+    }
+
+    fn dollars(value: i64) -> Currency {
+        Currency::from(('$', value))
+    }
+
+    #[test]
+    fn rounds_once() {
+        assert_eq!(dollars(1000) * qty("1.19"), dollars(1190));
+        assert_eq!(dollars(1000) * qty("1.1901"), dollars(1190));
+        assert_eq!(dollars(1000) * qty("1.1905"), dollars(1191));
+        assert_eq!(dollars(-1000) * qty("1.1905"), dollars(-1191));
+        assert_eq!(dollars(1) * qty("0.5"), dollars(1));
+        assert_eq!(
+            dollars(1).mul_quantity(qty("0.5"), RoundingMode::HalfEven),
+            dollars(0)
+        );
+        assert_eq!(
+            dollars(1).mul_quantity(qty("0.5"), RoundingMode::HalfDown),
+            dollars(0)
+        );
+        assert_eq!(
+            dollars(1).mul_quantity(qty("0.01"), RoundingMode::Up),
+            dollars(1)
+        );
+    }
+
+    #[test]
+    fn exact_where_floats_are_not() {
+        // 1.005 is slightly below itself as f64, so the float product rounds down
+        assert_eq!(dollars(100) * qty("1.005"), dollars(101));
+        assert_eq!(dollars(100) * 1.005, dollars(100));
+        let big = dollars(900_719_925_474_099);
+        assert_eq!(big * qty("10.01"), dollars(9_016_206_453_995_731));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn operators() {
+        let mut money = dollars(250);
+        money *= qty("2.5");
+        assert_eq!(money, dollars(625));
+        assert_eq!(qty("2.5") * dollars(250), dollars(625));
+        assert_eq!(&dollars(250) * &qty("2.5"), dollars(625));
+        assert_eq!(Currency::new() * qty("3.7"), Currency::new());
+    }
+
+    #[test]
+    fn overflow() {
+        let money = dollars(i64::MAX);
+        assert_eq!(
+            money.checked_mul_quantity(qty("1.5"), RoundingMode::HalfUp),
+            None
+        );
+        assert_eq!(
+            money.checked_mul_quantity(qty("0.5"), RoundingMode::Down),
+            Some(dollars(i64::MAX / 2))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Currency overflow")]
+    fn overflow_panics() {
+        let _ = dollars(i64::MIN) * qty("-1");
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(qty("2.50"), Quantity::new(Decimal::new(25, 1)));
+        assert_eq!(qty("0.333").to_string(), "0.333");
+        assert_eq!(Quantity::from(3), qty("3.0"));
+        assert!("2,5".parse::<Quantity>().is_err());
+    }
+}
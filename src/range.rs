@@ -0,0 +1,449 @@
+//! Ranges of amounts, e.g. "from €10 to €25".
+//!
+//! Both ends of a [`CurrencyRange`] share one symbol. Exclusive bounds are normalized to the
+//! nearest included amount in the smallest unit for all calculations, so `(€9.99, €25.00]` and
+//! `[€10.00, €25.00]` contain the same amounts.
+//!
+//! # Examples
+//! ```
+//! use claude::range::CurrencyRange;
+//! use claude::Currency;
+//!
+//! let euros = |value| Currency::from(('€', value));
+//! let range = CurrencyRange::inclusive(euros(1000), euros(2500)).unwrap();
+//!
+//! assert!(range.contains(&euros(1999)));
+//! assert!(!range.contains(&euros(2501)));
+//! assert_eq!(range.clamp(euros(3000)), Ok(euros(2500)));
+//! assert_eq!(range.to_string(), "€10.00–€25.00");
+//! assert_eq!(range.postfix().to_string(), "10,00€–25,00€");
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Bound, Range, RangeInclusive};
+
+use crate::{Currency, Symbol};
+
+/// Reasons why a range cannot be created or combined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RangeError {
+    /// The bounds or operands have different symbols.
+    CurrencyMismatch,
+    /// The range contains no amount.
+    Empty,
+    /// The ranges neither overlap nor touch, their union is not a range.
+    Disjoint,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            RangeError::CurrencyMismatch => "range bounds must share a currency",
+            RangeError::Empty => "range contains no amount",
+            RangeError::Disjoint => "ranges do not overlap",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for RangeError {}
+
+/// A non-empty range of amounts of one currency.
+///
+/// Ranges compare by the amounts they contain, `(€9.99, €25.00]` equals `[€10.00, €25.00]`.
+#[derive(Copy, Clone, Debug)]
+pub struct CurrencyRange {
+    symbol: Option<Symbol>,
+    start: Bound<i64>,
+    end: Bound<i64>,
+}
+
+impl PartialEq for CurrencyRange {
+    fn eq(&self, other: &CurrencyRange) -> bool {
+        self.symbol == other.symbol
+            && self.lowest() == other.lowest()
+            && self.highest() == other.highest()
+    }
+}
+
+impl Eq for CurrencyRange {}
+
+impl Hash for CurrencyRange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.symbol.hash(state);
+        self.lowest().hash(state);
+        self.highest().hash(state);
+    }
+}
+
+fn value_of(bound: Bound<Currency>) -> Bound<i64> {
+    match bound {
+        Bound::Included(money) => Bound::Included(money.value),
+        Bound::Excluded(money) => Bound::Excluded(money.value),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+fn symbol_of(bound: &Bound<Currency>) -> Option<Option<Symbol>> {
+    match bound {
+        Bound::Included(money) | Bound::Excluded(money) => Some(money.symbol),
+        Bound::Unbounded => None,
+    }
+}
+
+impl CurrencyRange {
+    /// Creates a range from two bounds of the same currency.
+    ///
+    /// A range with two unbounded ends has no symbol.
+    pub fn new(start: Bound<Currency>, end: Bound<Currency>) -> Result<CurrencyRange, RangeError> {
+        let symbol = match (symbol_of(&start), symbol_of(&end)) {
+            (Some(lhs), Some(rhs)) if lhs != rhs => return Err(RangeError::CurrencyMismatch),
+            (Some(symbol), _) | (_, Some(symbol)) => symbol,
+            (None, None) => None,
+        };
+        let range = CurrencyRange {
+            symbol,
+            start: value_of(start),
+            end: value_of(end),
+        };
+        if range.lowest() > range.highest() {
+            return Err(RangeError::Empty);
+        }
+        Ok(range)
+    }
+
+    /// Creates the range `start..=end`.
+    pub fn inclusive(start: Currency, end: Currency) -> Result<CurrencyRange, RangeError> {
+        CurrencyRange::new(Bound::Included(start), Bound::Included(end))
+    }
+
+    /// Creates the range `start..end`.
+    pub fn exclusive(start: Currency, end: Currency) -> Result<CurrencyRange, RangeError> {
+        CurrencyRange::new(Bound::Included(start), Bound::Excluded(end))
+    }
+
+    /// The symbol shared by both ends.
+    pub fn symbol(&self) -> Option<Symbol> {
+        self.symbol
+    }
+
+    /// The lower bound as given.
+    pub fn start(&self) -> Bound<Currency> {
+        self.with_symbol(self.start)
+    }
+
+    /// The upper bound as given.
+    pub fn end(&self) -> Bound<Currency> {
+        self.with_symbol(self.end)
+    }
+
+    /// Whether `money` lies within the range, amounts of other currencies never do.
+    pub fn contains(&self, money: &Currency) -> bool {
+        let value = i128::from(money.value);
+        money.symbol == self.symbol && self.lowest() <= value && value <= self.highest()
+    }
+
+    /// Moves `money` into the range, to the nearest included amount.
+    pub fn clamp(&self, money: Currency) -> Result<Currency, RangeError> {
+        self.check_symbol(money.symbol)?;
+        let value = i128::from(money.value).clamp(self.lowest(), self.highest());
+        Ok(Currency {
+            symbol: self.symbol,
+            value: value as i64,
+        })
+    }
+
+    /// The amounts contained in both ranges, `None` if there are none.
+    pub fn intersection(&self, other: &CurrencyRange) -> Result<Option<CurrencyRange>, RangeError> {
+        self.check_symbol(other.symbol)?;
+        let range = CurrencyRange {
+            symbol: self.symbol,
+            start: if self.lowest() >= other.lowest() {
+                self.start
+            } else {
+                other.start
+            },
+            end: if self.highest() <= other.highest() {
+                self.end
+            } else {
+                other.end
+            },
+        };
+        if range.lowest() > range.highest() {
+            return Ok(None);
+        }
+        Ok(Some(range))
+    }
+
+    /// The amounts contained in either range.
+    ///
+    /// The ranges have to overlap or touch, `[€1, €2]` and `[€2.01, €3]` join to `[€1, €3]`.
+    pub fn union(&self, other: &CurrencyRange) -> Result<CurrencyRange, RangeError> {
+        self.check_symbol(other.symbol)?;
+        if self.lowest() > other.highest() + 1 || other.lowest() > self.highest() + 1 {
+            return Err(RangeError::Disjoint);
+        }
+        Ok(CurrencyRange {
+            symbol: self.symbol,
+            start: if self.lowest() <= other.lowest() {
+                self.start
+            } else {
+                other.start
+            },
+            end: if self.highest() >= other.highest() {
+                self.end
+            } else {
+                other.end
+            },
+        })
+    }
+
+    /// Displays the range with [`Currency::postfix`], e.g. `10,00€–25,00€`.
+    pub fn postfix(&self) -> PostfixRange<'_> {
+        PostfixRange { range: self }
+    }
+
+    /// The lowest included value, bounds normalized to inclusive.
+    fn lowest(&self) -> i128 {
+        match self.start {
+            Bound::Included(value) => i128::from(value),
+            Bound::Excluded(value) => i128::from(value) + 1,
+            Bound::Unbounded => i128::from(i64::MIN),
+        }
+    }
+
+    /// The highest included value, bounds normalized to inclusive.
+    fn highest(&self) -> i128 {
+        match self.end {
+            Bound::Included(value) => i128::from(value),
+            Bound::Excluded(value) => i128::from(value) - 1,
+            Bound::Unbounded => i128::from(i64::MAX),
+        }
+    }
+
+    fn check_symbol(&self, symbol: Option<Symbol>) -> Result<(), RangeError> {
+        if symbol == self.symbol {
+            Ok(())
+        } else {
+            Err(RangeError::CurrencyMismatch)
+        }
+    }
+
+    fn with_symbol(&self, bound: Bound<i64>) -> Bound<Currency> {
+        let money = |value| Currency {
+            symbol: self.symbol,
+            value,
+        };
+        match bound {
+            Bound::Included(value) => Bound::Included(money(value)),
+            Bound::Excluded(value) => Bound::Excluded(money(value)),
+            Bound::Unbounded => Bound::Unbounded,
+        }
+    }
+
+    fn write_with<F>(&self, f: &mut fmt::Formatter<'_>, write_bound: F) -> fmt::Result
+    where
+        F: Fn(&mut fmt::Formatter<'_>, &Currency) -> fmt::Result,
+    {
+        let bracketed = !matches!(
+            (self.start, self.end),
+            (Bound::Included(_), Bound::Included(_))
+        );
+        if bracketed {
+            f.write_str(match self.start {
+                Bound::Included(_) => "[",
+                _ => "(",
+            })?;
+        }
+        if let Bound::Included(money) | Bound::Excluded(money) = self.start() {
+            write_bound(f, &money)?;
+        }
+        f.write_str("–")?;
+        if let Bound::Included(money) | Bound::Excluded(money) = self.end() {
+            write_bound(f, &money)?;
+        }
+        if bracketed {
+            f.write_str(match self.end {
+                Bound::Included(_) => "]",
+                _ => ")",
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays the range with [`Currency::prefix`], e.g. `$10.00–$25.00`.
+///
+/// Ranges that are not inclusive on both ends use interval notation, e.g. `[$10.00–$25.00)`,
+/// unbounded ends are left blank.
+impl fmt::Display for CurrencyRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_with(f, |f, money| write!(f, "{}", money.prefix()))
+    }
+}
+
+/// Displays a [`CurrencyRange`] with [`Currency::postfix`].
+#[derive(Debug)]
+pub struct PostfixRange<'a> {
+    range: &'a CurrencyRange,
+}
+
+impl<'a> fmt::Display for PostfixRange<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.range
+            .write_with(f, |f, money| write!(f, "{}", money.postfix()))
+    }
+}
+
+impl TryFrom<RangeInclusive<Currency>> for CurrencyRange {
+    type Error = RangeError;
+
+    fn try_from(range: RangeInclusive<Currency>) -> Result<CurrencyRange, RangeError> {
+        let (start, end) = range.into_inner();
+        CurrencyRange::inclusive(start, end)
+    }
+}
+
+impl TryFrom<Range<Currency>> for CurrencyRange {
+    type Error = RangeError;
+
+    fn try_from(range: Range<Currency>) -> Result<CurrencyRange, RangeError> {
+        CurrencyRange::exclusive(range.start, range.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dollars(value: i64) -> Currency {
+        Currency::from(('$', value))
+    }
+
+    fn range(start: i64, end: i64) -> CurrencyRange {
+        CurrencyRange::inclusive(dollars(start), dollars(end)).unwrap()
+    }
+
+    #[test]
+    fn construction() {
+        assert_eq!(
+            CurrencyRange::inclusive(dollars(1), Currency::from(('€', 2))),
+            Err(RangeError::CurrencyMismatch)
+        );
+        assert_eq!(
+            CurrencyRange::inclusive(dollars(2), dollars(1)),
+            Err(RangeError::Empty)
+        );
+        assert_eq!(
+            CurrencyRange::exclusive(dollars(1), dollars(1)),
+            Err(RangeError::Empty)
+        );
+        assert_eq!(
+            CurrencyRange::try_from(dollars(1)..=dollars(1)),
+            Ok(range(1, 1))
+        );
+        let open = CurrencyRange::new(Bound::Unbounded, Bound::Excluded(dollars(5))).unwrap();
+        assert_eq!(open.symbol(), Some('$'));
+        assert_eq!(open.end(), Bound::Excluded(dollars(5)));
+    }
+
+    #[test]
+    fn exclusive_bounds_are_normalized() {
+        let half_open = CurrencyRange::try_from(dollars(1000)..dollars(2500)).unwrap();
+        assert!(half_open.contains(&dollars(2499)));
+        assert!(!half_open.contains(&dollars(2500)));
+        assert_eq!(half_open.clamp(dollars(9999)), Ok(dollars(2499)));
+
+        let open = CurrencyRange::new(Bound::Excluded(dollars(999)), Bound::Unbounded).unwrap();
+        assert!(!open.contains(&dollars(999)));
+        assert!(open.contains(&dollars(i64::MAX)));
+        assert_eq!(open.clamp(dollars(i64::MIN)), Ok(dollars(1000)));
+    }
+
+    #[test]
+    fn equality_uses_normalized_bounds() {
+        use std::collections::HashSet;
+
+        let exclusive = CurrencyRange::new(
+            Bound::Excluded(dollars(999)),
+            Bound::Included(dollars(2500)),
+        )
+        .unwrap();
+        let inclusive = CurrencyRange::inclusive(dollars(1000), dollars(2500)).unwrap();
+        assert_eq!(exclusive, inclusive);
+        assert_eq!(exclusive.start(), Bound::Excluded(dollars(999)));
+        assert_eq!(
+            CurrencyRange::exclusive(dollars(1000), dollars(2501)).unwrap(),
+            inclusive
+        );
+        assert_ne!(
+            CurrencyRange::exclusive(dollars(1000), dollars(2500)).unwrap(),
+            inclusive
+        );
+        let euros =
+            CurrencyRange::inclusive(Currency::from(('€', 1000)), Currency::from(('€', 2500)));
+        assert_ne!(euros.unwrap(), inclusive);
+
+        let set: HashSet<CurrencyRange> = vec![exclusive, inclusive].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn contains_and_clamp_other_currencies() {
+        assert!(!range(0, 100).contains(&Currency::from(50)));
+        assert_eq!(
+            range(0, 100).clamp(Currency::from(('€', 50))),
+            Err(RangeError::CurrencyMismatch)
+        );
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!(
+            range(0, 100).intersection(&range(50, 150)),
+            Ok(Some(range(50, 100)))
+        );
+        assert_eq!(range(0, 100).intersection(&range(101, 150)), Ok(None));
+        let half_open = CurrencyRange::exclusive(dollars(0), dollars(100)).unwrap();
+        assert_eq!(
+            range(50, 100).intersection(&half_open),
+            Ok(Some(
+                CurrencyRange::exclusive(dollars(50), dollars(100)).unwrap()
+            ))
+        );
+        assert_eq!(range(100, 100).intersection(&half_open), Ok(None));
+    }
+
+    #[test]
+    fn union() {
+        assert_eq!(range(0, 100).union(&range(50, 150)), Ok(range(0, 150)));
+        assert_eq!(range(0, 100).union(&range(101, 150)), Ok(range(0, 150)));
+        assert_eq!(
+            range(0, 100).union(&range(102, 150)),
+            Err(RangeError::Disjoint)
+        );
+        let half_open = CurrencyRange::exclusive(dollars(0), dollars(100)).unwrap();
+        assert_eq!(half_open.union(&range(101, 150)), Err(RangeError::Disjoint));
+        assert_eq!(
+            range(0, 100)
+                .union(&CurrencyRange::inclusive(Currency::from(0), Currency::from(1)).unwrap()),
+            Err(RangeError::CurrencyMismatch)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(range(1000, 2500).to_string(), "$10.00–$25.00");
+        assert_eq!(
+            CurrencyRange::exclusive(dollars(1000), dollars(2500))
+                .unwrap()
+                .to_string(),
+            "[$10.00–$25.00)"
+        );
+        let from = CurrencyRange::new(Bound::Included(dollars(1000)), Bound::Unbounded).unwrap();
+        assert_eq!(from.to_string(), "[$10.00–)");
+        assert_eq!(from.postfix().to_string(), "[10,00$–)");
+    }
+}
@@ -0,0 +1,212 @@
+//! Exact ratios between two amounts of the same currency.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::decimal::{pow10, Decimal, MAX_SCALE};
+use crate::rounding::{div_round, RoundingMode};
+use crate::Currency;
+
+/// Reasons why two currencies cannot be divided.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RatioError {
+    /// The currencies have different symbols.
+    CurrencyMismatch,
+    /// The divisor is zero.
+    DivisionByZero,
+}
+
+impl fmt::Display for RatioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RatioError::CurrencyMismatch => f.write_str("cannot divide different currencies"),
+            RatioError::DivisionByZero => f.write_str("cannot divide by a zero amount"),
+        }
+    }
+}
+
+impl Error for RatioError {}
+
+/// An exact fraction in lowest terms with a positive denominator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    numerator: i128,
+    denominator: i128,
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        let rest = a % b;
+        a = b;
+        b = rest;
+    }
+    a.abs()
+}
+
+impl Ratio {
+    fn new(numerator: i128, denominator: i128) -> Ratio {
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Ratio {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    /// The numerator, carrying the sign of the ratio.
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    /// The denominator, always positive.
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    /// Rounds the ratio to a decimal with `places` fractional digits.
+    ///
+    /// Returns `None` if `places` exceeds [`MAX_SCALE`] or the result does not fit.
+    pub fn to_decimal(&self, places: u32, mode: RoundingMode) -> Option<Decimal> {
+        if places > MAX_SCALE {
+            return None;
+        }
+        let numerator = self.numerator.checked_mul(pow10(places))?;
+        Some(Decimal::new(
+            div_round(numerator, self.denominator, mode),
+            places,
+        ))
+    }
+
+    /// Rounds the ratio expressed in percent to `places` fractional digits.
+    pub fn to_percent(&self, places: u32, mode: RoundingMode) -> Option<Decimal> {
+        let ratio = Ratio::new(self.numerator.checked_mul(100)?, self.denominator);
+        ratio.to_decimal(places, mode)
+    }
+
+    /// Returns the ratio as float
+    ///
+    /// # Warning, do not use this for calculation, this is for displaying only!
+    pub fn as_float(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+/// Displays the fraction, e.g. `3/4`
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+/// Ratios between amounts.
+impl Currency {
+    /// Divides `self` by `divisor` exactly, e.g. the fraction of a budget that is spent.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{ratio::RatioError, rounding::RoundingMode, decimal::Decimal, Currency};
+    ///
+    /// let spent = Currency::from(('€', 37500));
+    /// let budget = Currency::from(('€', 100000));
+    ///
+    /// let ratio = spent.ratio(&budget).unwrap();
+    /// assert_eq!((ratio.numerator(), ratio.denominator()), (3, 8));
+    /// assert_eq!(ratio.to_decimal(2, RoundingMode::HalfEven), Some(Decimal::new(38, 2)));
+    /// assert_eq!(ratio.to_percent(1, RoundingMode::HalfEven), Some(Decimal::new(375, 1)));
+    ///
+    /// assert_eq!(spent.ratio(&Currency::from(('$', 1))), Err(RatioError::CurrencyMismatch));
+    /// assert_eq!(spent.ratio(&Currency::from(('€', 0))), Err(RatioError::DivisionByZero));
+    /// ```
+    pub fn ratio(&self, divisor: &Currency) -> Result<Ratio, RatioError> {
+        if self.symbol != divisor.symbol {
+            return Err(RatioError::CurrencyMismatch);
+        }
+        if divisor.value == 0 {
+            return Err(RatioError::DivisionByZero);
+        }
+        Ok(Ratio::new(
+            i128::from(self.value),
+            i128::from(divisor.value),
+        ))
+    }
+
+    /// The relative change from `self` to `new`, i.e. `(new - self) / self`.
+    ///
+    /// The change is relative to the magnitude of `self`, so going from `-100` to `-50` is an
+    /// increase of 50%.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::{rounding::RoundingMode, decimal::Decimal, Currency};
+    ///
+    /// let last_year = Currency::from(('$', 80000));
+    /// let this_year = Currency::from(('$', 90000));
+    ///
+    /// let change = last_year.percentage_change(&this_year).unwrap();
+    /// assert_eq!(change.to_percent(2, RoundingMode::HalfUp), Some(Decimal::new(1250, 2)));
+    /// ```
+    pub fn percentage_change(&self, new: &Currency) -> Result<Ratio, RatioError> {
+        if self.symbol != new.symbol {
+            return Err(RatioError::CurrencyMismatch);
+        }
+        if self.value == 0 {
+            return Err(RatioError::DivisionByZero);
+        }
+        let old = i128::from(self.value);
+        Ok(Ratio::new(i128::from(new.value) - old, old.abs()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dollars(value: i64) -> Currency {
+        Currency::from(('$', value))
+    }
+
+    #[test]
+    fn lowest_terms() {
+        let ratio = dollars(-250).ratio(&dollars(-1000)).unwrap();
+        assert_eq!((ratio.numerator(), ratio.denominator()), (1, 4));
+        let ratio = dollars(250).ratio(&dollars(-1000)).unwrap();
+        assert_eq!((ratio.numerator(), ratio.denominator()), (-1, 4));
+        let ratio = dollars(0).ratio(&dollars(-1000)).unwrap();
+        assert_eq!((ratio.numerator(), ratio.denominator()), (0, 1));
+        let ratio = dollars(i64::MIN).ratio(&dollars(-1)).unwrap();
+        assert_eq!(ratio.numerator(), -i128::from(i64::MIN));
+    }
+
+    #[test]
+    fn decimal_precision() {
+        let third = dollars(100).ratio(&dollars(300)).unwrap();
+        assert_eq!(
+            third
+                .to_decimal(4, RoundingMode::HalfUp)
+                .unwrap()
+                .to_string(),
+            "0.3333"
+        );
+        assert_eq!(
+            third.to_decimal(0, RoundingMode::Up).unwrap().to_string(),
+            "1"
+        );
+        assert_eq!(third.to_decimal(MAX_SCALE + 1, RoundingMode::Up), None);
+        assert_eq!(third.to_string(), "1/3");
+    }
+
+    #[test]
+    fn percentage_change() {
+        let change = |from, to| {
+            dollars(from)
+                .percentage_change(&dollars(to))
+                .map(|ratio| ratio.to_percent(1, RoundingMode::HalfUp).unwrap())
+        };
+        assert_eq!(change(200, 150), Ok(Decimal::new(-250, 1)));
+        assert_eq!(change(-100, -50), Ok(Decimal::new(500, 1)));
+        assert_eq!(change(0, 50), Err(RatioError::DivisionByZero));
+        assert_eq!(
+            dollars(1).percentage_change(&Currency::from(1)),
+            Err(RatioError::CurrencyMismatch)
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Rounding modes for operations whose result does not fit into the smallest unit.
+
+/// How to round a result that lies between two representable values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Away from zero: `1.1 -> 2`, `-1.1 -> -2`
+    Up,
+    /// Towards zero (truncation): `1.9 -> 1`, `-1.9 -> -1`
+    Down,
+    /// Towards positive infinity: `1.1 -> 2`, `-1.9 -> -1`
+    Ceiling,
+    /// Towards negative infinity: `1.9 -> 1`, `-1.1 -> -2`
+    Floor,
+    /// To the nearest value, ties away from zero (commercial rounding): `2.5 -> 3`, `-2.5 -> -3`
+    HalfUp,
+    /// To the nearest value, ties towards zero: `2.5 -> 2`, `-2.5 -> -2`
+    HalfDown,
+    /// To the nearest value, ties to the even neighbour (banker's rounding): `2.5 -> 2`, `3.5 -> 4`
+    HalfEven,
+}
+
+/// Defines a function dividing `numerator` by `denominator` and rounding the quotient according to
+/// `mode`, for one integer type.
+macro_rules! div_round {
+    ($(#[$attr:meta])* $name:ident, $int:ty) => {
+        $(#[$attr])*
+        ///
+        /// # Panics
+        /// Panics if `denominator` is zero.
+        pub(crate) fn $name(numerator: $int, denominator: $int, mode: RoundingMode) -> $int {
+            let quotient = numerator / denominator;
+            let remainder = numerator % denominator;
+            if remainder == 0 {
+                return quotient;
+            }
+
+            let away = if (numerator < 0) == (denominator < 0) {
+                quotient + 1
+            } else {
+                quotient - 1
+            };
+            let remainder = remainder.unsigned_abs();
+            let rest = denominator.unsigned_abs() - remainder;
+
+            let round_away = match mode {
+                RoundingMode::Up => true,
+                RoundingMode::Down => false,
+                RoundingMode::Ceiling => away > quotient,
+                RoundingMode::Floor => away < quotient,
+                RoundingMode::HalfUp => remainder >= rest,
+                RoundingMode::HalfDown => remainder > rest,
+                RoundingMode::HalfEven => {
+                    remainder > rest || (remainder == rest && quotient % 2 != 0)
+                }
+            };
+
+            if round_away {
+                away
+            } else {
+                quotient
+            }
+        }
+    };
+}
+
+div_round!(
+    /// Divides `numerator` by `denominator` and rounds the quotient according to `mode`.
+    div_round,
+    i128
+);
+div_round!(
+    /// Like [`div_round`], avoiding the slower 128 bit division where the operands fit.
+    div_round_i64,
+    i64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::RoundingMode::*;
+    use super::*;
+
+    #[test]
+    fn modes() {
+        let round = |n, mode| div_round(n, 10, mode);
+        let cases: &[(i128, [i128; 7])] = &[
+            //     Up, Down, Ceiling, Floor, HalfUp, HalfDown, HalfEven
+            (11, [2, 1, 2, 1, 1, 1, 1]),
+            (15, [2, 1, 2, 1, 2, 1, 2]),
+            (25, [3, 2, 3, 2, 3, 2, 2]),
+            (19, [2, 1, 2, 1, 2, 2, 2]),
+            (-11, [-2, -1, -1, -2, -1, -1, -1]),
+            (-15, [-2, -1, -1, -2, -2, -1, -2]),
+            (-25, [-3, -2, -2, -3, -3, -2, -2]),
+            (20, [2, 2, 2, 2, 2, 2, 2]),
+        ];
+        let modes = [Up, Down, Ceiling, Floor, HalfUp, HalfDown, HalfEven];
+        for (n, expected) in cases {
+            for (mode, expected) in modes.iter().zip(expected.iter()) {
+                assert_eq!(round(*n, *mode), *expected, "{} with {:?}", n, mode);
+                assert_eq!(
+                    i128::from(div_round_i64(*n as i64, 10, *mode)),
+                    *expected,
+                    "{} with {:?}",
+                    n,
+                    mode
+                );
+            }
+        }
+        assert_eq!(div_round(15, -10, HalfUp), -2);
+        assert_eq!(div_round(-15, -10, Floor), 1);
+    }
+}
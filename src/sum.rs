@@ -1,32 +1,91 @@
 //! Implementation of `std::iter::Sum`
+//!
+//! Summing follows the symbol rules of `+`: all amounts must share a symbol, an empty iterator
+//! sums up to `Currency::default()`. For large slices see [`batch`](crate::batch).
 
-use ::Currency;
+use crate::Currency;
 
 use std::iter::Sum;
+
 impl Sum for Currency {
+    fn sum<I>(mut iter: I) -> Self
+    where
+        I: Iterator<Item = Currency>,
+    {
+        match iter.next() {
+            Some(first) => iter.fold(first, |acc, x| acc + x),
+            None => Currency::default(),
+        }
+    }
+}
+
+impl<'a> Sum<&'a Currency> for Currency {
     fn sum<I>(iter: I) -> Self
-        where I: Iterator<Item = Currency>
+    where
+        I: Iterator<Item = &'a Currency>,
     {
-        iter.fold(Currency::default(), |acc, x| acc + x)
+        iter.copied().sum()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use ::Currency;
+    use crate::Currency;
+
     #[test]
     fn sum_up() {
         let list = vec![
-            Currency{symbol: Some('£'), value: 1000},
-            Currency{symbol: Some('£'), value: 0100},
-            Currency{symbol: Some('£'), value: 0010},
-            Currency{symbol: Some('£'), value: 0001},
+            Currency {
+                symbol: Some('£'),
+                value: 1000,
+            },
+            Currency {
+                symbol: Some('£'),
+                value: 100,
+            },
+            Currency {
+                symbol: Some('£'),
+                value: 10,
+            },
+            Currency {
+                symbol: Some('£'),
+                value: 1,
+            },
         ];
 
-        let fold = list.iter().fold(Currency::default(), |acc, x| acc + *x);
-        assert_eq!(fold, Currency{ symbol: Some('£'), value:1111});
+        let fold = list.iter().skip(1).fold(list[0], |acc, x| acc + *x);
+        assert_eq!(
+            fold,
+            Currency {
+                symbol: Some('£'),
+                value: 1111
+            }
+        );
+
+        let by_ref: Currency = list.iter().sum();
+        assert_eq!(by_ref, fold);
 
-        let sum:Currency = list.into_iter().sum();
-        assert_eq!(sum, Currency{ symbol: Some('£'), value:1111});
+        let sum: Currency = list.into_iter().sum();
+        assert_eq!(
+            sum,
+            Currency {
+                symbol: Some('£'),
+                value: 1111
+            }
+        );
+    }
+
+    #[test]
+    fn empty_is_zero() {
+        let sum: Currency = Vec::<Currency>::new().into_iter().sum();
+        assert_eq!(sum, Currency::default());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_symbols_panic() {
+        let _: Currency = vec![Currency::from(('£', 1)), Currency::from(('€', 1))]
+            .into_iter()
+            .sum();
     }
 }
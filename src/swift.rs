@@ -0,0 +1,763 @@
+//! Amount fields of SWIFT MT messages and MT940 bank statements.
+//!
+//! SWIFT amounts (`15d`) are unsigned, use a comma as decimal mark, e.g. `1234,56` or `1500,`, and
+//! have at most 15 characters. The currency is a three letter code in an adjacent field, the sign
+//! a debit/credit [`Mark`].
+//!
+//! Codes are looked up with [`CurrencyInfo::from_code`], currencies without a symbol are not
+//! supported.
+//!
+//! # Examples
+//! ```
+//! use claude::{swift, Currency};
+//!
+//! assert_eq!(swift::parse_signed("D", "EUR", "1234,56"), Ok(Currency::from(('€', -123_456))));
+//! assert_eq!(
+//!     swift::format_signed(Currency::from(('€', -123_456))),
+//!     Ok((swift::Mark::Debit, "1234,56".to_string()))
+//! );
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::info::CurrencyInfo;
+use crate::{Currency, Symbol};
+
+/// Largest number of characters of an amount, including the decimal comma.
+pub const MAX_AMOUNT_LENGTH: usize = 15;
+
+/// Reasons why a SWIFT field cannot be read or written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SwiftError {
+    /// The amount is not digits with a decimal comma, or longer than [`MAX_AMOUNT_LENGTH`].
+    InvalidAmount,
+    /// The amount has fractions of the smallest unit.
+    PrecisionLoss,
+    /// The amount does not fit into [`MAX_AMOUNT_LENGTH`] characters.
+    TooLarge,
+    /// Unsigned amount fields cannot hold negative amounts.
+    Negative,
+    /// The debit/credit mark is not one of `C`, `D`, `RC` or `RD`.
+    InvalidMark,
+    /// The date is not a valid `YYMMDD` date from 2000 to 2099.
+    InvalidDate,
+    /// The currency code is unknown or the currency has no symbol.
+    UnknownCurrency,
+    /// A field of a statement is missing, named by its tag.
+    MissingField(&'static str),
+    /// A field of a statement is malformed, named by its tag.
+    InvalidField(&'static str),
+}
+
+impl fmt::Display for SwiftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            SwiftError::InvalidAmount => "not a SWIFT amount like 1234,56",
+            SwiftError::PrecisionLoss => "amount has fractions of the smallest unit",
+            SwiftError::TooLarge => "amount too large for a SWIFT amount field",
+            SwiftError::Negative => "SWIFT amounts are unsigned",
+            SwiftError::InvalidMark => "not a debit/credit mark",
+            SwiftError::InvalidDate => "not a valid YYMMDD date",
+            SwiftError::UnknownCurrency => "unknown currency code",
+            SwiftError::MissingField(tag) => return write!(f, "missing field :{}:", tag),
+            SwiftError::InvalidField(tag) => return write!(f, "malformed field :{}:", tag),
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for SwiftError {}
+
+/// A calendar date, written as `YYMMDD` in SWIFT fields.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year, 2000 to 2099 in SWIFT fields.
+    pub year: u16,
+    /// The month, 1 to 12.
+    pub month: u8,
+    /// The day of the month, starting at 1.
+    pub day: u8,
+}
+
+// `is_multiple_of` is too new for the supported compilers
+#[allow(clippy::manual_is_multiple_of)]
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Reads two ASCII digits.
+fn two_digits(text: &str) -> Option<u8> {
+    match text.as_bytes() {
+        [tens, ones] if tens.is_ascii_digit() && ones.is_ascii_digit() => {
+            Some((tens - b'0') * 10 + (ones - b'0'))
+        }
+        _ => None,
+    }
+}
+
+impl Date {
+    /// Creates a date, `None` if it does not exist.
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Date> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    /// Parses `YYMMDD`, with years from 2000 to 2099.
+    pub fn from_yymmdd(text: &str) -> Result<Date, SwiftError> {
+        let part = |range| text.get(range).and_then(two_digits);
+        match (text.len(), part(0..2), part(2..4), part(4..6)) {
+            (6, Some(year), Some(month), Some(day)) => {
+                Date::new(2000 + u16::from(year), month, day).ok_or(SwiftError::InvalidDate)
+            }
+            _ => Err(SwiftError::InvalidDate),
+        }
+    }
+
+    /// Formats as `YYMMDD`, failing for years before 2000 or after 2099.
+    pub fn to_yymmdd(&self) -> Result<String, SwiftError> {
+        if self.year < 2000 || self.year > 2099 {
+            return Err(SwiftError::InvalidDate);
+        }
+        Ok(format!(
+            "{:02}{:02}{:02}",
+            self.year % 100,
+            self.month,
+            self.day
+        ))
+    }
+}
+
+/// Debit/credit mark giving the sign of an amount.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Mark {
+    /// `C`, money coming in.
+    Credit,
+    /// `D`, money going out.
+    Debit,
+    /// `RC`, the reversal of a credit, so money going out.
+    ReversalOfCredit,
+    /// `RD`, the reversal of a debit, so money coming in.
+    ReversalOfDebit,
+}
+
+impl Mark {
+    /// Reads `C`, `D`, `RC` or `RD`.
+    pub fn from_code(code: &str) -> Result<Mark, SwiftError> {
+        match code {
+            "C" => Ok(Mark::Credit),
+            "D" => Ok(Mark::Debit),
+            "RC" => Ok(Mark::ReversalOfCredit),
+            "RD" => Ok(Mark::ReversalOfDebit),
+            _ => Err(SwiftError::InvalidMark),
+        }
+    }
+
+    /// The code of the mark.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Mark::Credit => "C",
+            Mark::Debit => "D",
+            Mark::ReversalOfCredit => "RC",
+            Mark::ReversalOfDebit => "RD",
+        }
+    }
+
+    /// Whether the mark makes an amount negative.
+    pub fn is_negative(&self) -> bool {
+        matches!(self, Mark::Debit | Mark::ReversalOfCredit)
+    }
+
+    fn apply(&self, money: Currency) -> Currency {
+        if self.is_negative() {
+            Currency {
+                symbol: money.symbol,
+                value: -money.value,
+            }
+        } else {
+            money
+        }
+    }
+}
+
+fn symbol(code: &str) -> Result<Symbol, SwiftError> {
+    if !code.bytes().all(|b| b.is_ascii_uppercase()) {
+        return Err(SwiftError::UnknownCurrency);
+    }
+    CurrencyInfo::from_code(code)
+        .and_then(CurrencyInfo::symbol)
+        .ok_or(SwiftError::UnknownCurrency)
+}
+
+fn code(money: &Currency) -> Result<&'static str, SwiftError> {
+    money
+        .info()
+        .map(CurrencyInfo::code)
+        .ok_or(SwiftError::UnknownCurrency)
+}
+
+/// Reads the digits of an amount, in the smallest unit.
+fn parse_magnitude(amount: &str) -> Result<i64, SwiftError> {
+    if amount.len() > MAX_AMOUNT_LENGTH {
+        return Err(SwiftError::InvalidAmount);
+    }
+    let comma = amount.find(',').ok_or(SwiftError::InvalidAmount)?;
+    let (int, frac) = (&amount[..comma], &amount[comma + 1..]);
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if int.is_empty() || !all_digits(int) || !all_digits(frac) {
+        return Err(SwiftError::InvalidAmount);
+    }
+    let frac = frac.trim_end_matches('0');
+    if frac.len() > 2 {
+        return Err(SwiftError::PrecisionLoss);
+    }
+    // at most 14 digits, so this cannot overflow
+    let whole: i64 = int.parse().map_err(|_| SwiftError::InvalidAmount)?;
+    let cents = format!("{:0<2}", frac).parse::<i64>().unwrap_or(0);
+    Ok(whole * 100 + cents)
+}
+
+/// Parses an unsigned amount like `1234,56` in the currency with the given code.
+///
+/// # Examples
+/// ```
+/// use claude::{swift, Currency};
+///
+/// assert_eq!(swift::parse_amount("GBP", "1500,"), Ok(Currency::from(('£', 150_000))));
+/// assert_eq!(swift::parse_amount("GBP", "0,5"), Ok(Currency::from(('£', 50))));
+/// assert!(swift::parse_amount("GBP", "1.500,00").is_err());
+/// ```
+pub fn parse_amount(code: &str, amount: &str) -> Result<Currency, SwiftError> {
+    let symbol = symbol(code)?;
+    Ok(Currency::from((symbol, parse_magnitude(amount)?)))
+}
+
+/// Parses an amount with its debit/credit mark, debits and reversals of credits are negative.
+pub fn parse_signed(mark: &str, code: &str, amount: &str) -> Result<Currency, SwiftError> {
+    let mark = Mark::from_code(mark)?;
+    Ok(mark.apply(parse_amount(code, amount)?))
+}
+
+/// Formats a non-negative amount like `1234,56`.
+pub fn format_amount(money: Currency) -> Result<String, SwiftError> {
+    if money.value < 0 {
+        return Err(SwiftError::Negative);
+    }
+    let amount = format!("{},{:02}", money.value / 100, money.value % 100);
+    if amount.len() > MAX_AMOUNT_LENGTH {
+        return Err(SwiftError::TooLarge);
+    }
+    Ok(amount)
+}
+
+/// Formats the magnitude of an amount and the [`Mark::Debit`] or [`Mark::Credit`] for its sign.
+pub fn format_signed(money: Currency) -> Result<(Mark, String), SwiftError> {
+    let mark = if money.value < 0 {
+        Mark::Debit
+    } else {
+        Mark::Credit
+    };
+    let magnitude = money.value.checked_abs().ok_or(SwiftError::TooLarge)?;
+    let amount = format_amount(Currency {
+        symbol: money.symbol,
+        value: magnitude,
+    })?;
+    Ok((mark, amount))
+}
+
+/// Parses a currency code followed by an amount, e.g. `USD1234,56` in the fields `:33B:` or
+/// `:71F:` of an MT103.
+pub fn parse_currency_amount(field: &str) -> Result<Currency, SwiftError> {
+    let code = field.get(..3).ok_or(SwiftError::UnknownCurrency)?;
+    parse_amount(code, &field[3..])
+}
+
+/// Formats a currency code followed by an amount, e.g. `USD1234,56`.
+pub fn format_currency_amount(money: Currency) -> Result<String, SwiftError> {
+    Ok(format!("{}{}", code(&money)?, format_amount(money)?))
+}
+
+/// Parses the field `:32A:` of an MT103, value date, currency and amount, e.g.
+/// `230115EUR1234,56`.
+///
+/// # Examples
+/// ```
+/// use claude::{swift::{self, Date}, Currency};
+///
+/// assert_eq!(
+///     swift::parse_32a("230115EUR1234,56"),
+///     Ok((Date::new(2023, 1, 15).unwrap(), Currency::from(('€', 123_456))))
+/// );
+/// ```
+pub fn parse_32a(field: &str) -> Result<(Date, Currency), SwiftError> {
+    let date = Date::from_yymmdd(field.get(..6).ok_or(SwiftError::InvalidDate)?)?;
+    Ok((date, parse_currency_amount(&field[6..])?))
+}
+
+/// Formats the field `:32A:` of an MT103, e.g. `230115EUR1234,56`.
+pub fn format_32a(date: Date, money: Currency) -> Result<String, SwiftError> {
+    Ok(format!(
+        "{}{}",
+        date.to_yymmdd()?,
+        format_currency_amount(money)?
+    ))
+}
+
+/// A balance of an MT940 statement, fields `:60F:`, `:62F:`, `:64:` and their variants.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Balance {
+    /// The date of the balance.
+    pub date: Date,
+    /// The balance, negative for a debit balance.
+    pub amount: Currency,
+}
+
+/// Parses a balance like `C230113EUR1000,00`.
+pub fn parse_balance(field: &str) -> Result<Balance, SwiftError> {
+    let mark = field.get(..1).ok_or(SwiftError::InvalidMark)?;
+    if mark != "C" && mark != "D" {
+        return Err(SwiftError::InvalidMark);
+    }
+    let date = Date::from_yymmdd(field.get(1..7).ok_or(SwiftError::InvalidDate)?)?;
+    let code = field.get(7..10).ok_or(SwiftError::UnknownCurrency)?;
+    let amount = parse_signed(mark, code, &field[10..])?;
+    Ok(Balance { date, amount })
+}
+
+/// A transaction of an MT940 statement, field `:61:` and the following `:86:`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatementLine {
+    /// The value date.
+    pub value_date: Date,
+    /// The booking date, if given.
+    pub entry_date: Option<Date>,
+    /// The debit/credit mark.
+    pub mark: Mark,
+    /// The third letter of the currency code, if given.
+    pub funds_code: Option<char>,
+    /// The amount, negative for debits and reversals of credits.
+    pub amount: Currency,
+    /// The transaction type and identification code, e.g. `NTRF`.
+    pub transaction_type: String,
+    /// The reference for the account owner, `NONREF` if there is none.
+    pub customer_reference: String,
+    /// The reference of the bank, if given.
+    pub bank_reference: Option<String>,
+    /// The second line of the field, if given.
+    pub supplementary_details: Option<String>,
+    /// The content of the field `:86:` following the line, if given.
+    pub information: Option<String>,
+}
+
+/// The year of the `MMDD` entry date, which may lie in the year before or after the value date.
+fn entry_date(value_date: Date, mmdd: &str) -> Result<Date, SwiftError> {
+    let month = mmdd.get(..2).and_then(two_digits);
+    let day = mmdd.get(2..).and_then(two_digits);
+    let (month, day) = month.zip(day).ok_or(SwiftError::InvalidDate)?;
+    let year = if month > value_date.month + 6 {
+        value_date.year - 1
+    } else if month + 6 < value_date.month {
+        value_date.year + 1
+    } else {
+        value_date.year
+    };
+    Date::new(year, month, day).ok_or(SwiftError::InvalidDate)
+}
+
+/// Parses the content of a `:61:` field, e.g. `2301160116D250,50NTRFNONREF//B123456789`, in the
+/// currency with the given code.
+///
+/// # Examples
+/// ```
+/// use claude::{swift::{self, Mark}, Currency};
+///
+/// let line = swift::parse_statement_line("2301160116CR1500,NMSCREF-0042", "EUR").unwrap();
+/// assert_eq!(line.mark, Mark::Credit);
+/// assert_eq!(line.funds_code, Some('R'));
+/// assert_eq!(line.amount, Currency::from(('€', 150_000)));
+/// assert_eq!(line.customer_reference, "REF-0042");
+/// ```
+pub fn parse_statement_line(field: &str, code: &str) -> Result<StatementLine, SwiftError> {
+    let invalid = SwiftError::InvalidField("61");
+    let (first, supplementary_details) = match field.find('\n') {
+        Some(end) => (&field[..end], Some(field[end + 1..].to_string())),
+        None => (field, None),
+    };
+    let value_date = Date::from_yymmdd(first.get(..6).ok_or(SwiftError::InvalidDate)?)?;
+    let mut rest = &first[6..];
+
+    let entry = rest
+        .get(..4)
+        .filter(|mmdd| mmdd.bytes().all(|b| b.is_ascii_digit()));
+    let entry_date = match entry {
+        Some(mmdd) => {
+            rest = &rest[4..];
+            Some(entry_date(value_date, mmdd)?)
+        }
+        None => None,
+    };
+
+    let mark_length = if rest.starts_with("RC") || rest.starts_with("RD") {
+        2
+    } else {
+        1
+    };
+    let mark = Mark::from_code(rest.get(..mark_length).ok_or(SwiftError::InvalidMark)?)?;
+    rest = &rest[mark_length..];
+
+    let funds_code = rest.chars().next().filter(char::is_ascii_alphabetic);
+    if funds_code.is_some() {
+        rest = &rest[1..];
+    }
+
+    let amount_length = rest
+        .find(|c: char| !c.is_ascii_digit() && c != ',')
+        .unwrap_or(rest.len());
+    let amount = mark.apply(parse_amount(code, &rest[..amount_length])?);
+    rest = &rest[amount_length..];
+
+    let transaction_type = rest
+        .get(..4)
+        .filter(|kind| kind.bytes().all(|b| b.is_ascii_alphanumeric()))
+        .ok_or_else(|| invalid.clone())?;
+    rest = &rest[4..];
+
+    let (customer_reference, bank_reference) = match rest.find("//") {
+        Some(pos) => (&rest[..pos], Some(rest[pos + 2..].to_string())),
+        None => (rest, None),
+    };
+    if customer_reference.is_empty() {
+        return Err(invalid);
+    }
+
+    Ok(StatementLine {
+        value_date,
+        entry_date,
+        mark,
+        funds_code,
+        amount,
+        transaction_type: transaction_type.to_string(),
+        customer_reference: customer_reference.to_string(),
+        bank_reference,
+        supplementary_details,
+        information: None,
+    })
+}
+
+/// An MT940 customer statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Statement {
+    /// The transaction reference, field `:20:`.
+    pub reference: String,
+    /// The account, field `:25:`.
+    pub account: String,
+    /// The statement and sequence number, field `:28C:`.
+    pub sequence: Option<String>,
+    /// The opening balance, field `:60F:` or `:60M:`.
+    pub opening: Balance,
+    /// The transactions.
+    pub lines: Vec<StatementLine>,
+    /// The closing balance, field `:62F:` or `:62M:`.
+    pub closing: Balance,
+    /// The closing available balance, field `:64:`.
+    pub available: Option<Balance>,
+    /// Information for the account owner not belonging to a transaction, field `:86:`.
+    pub information: Option<String>,
+}
+
+impl Statement {
+    /// Whether the opening balance plus all transactions gives the closing balance.
+    pub fn is_balanced(&self) -> bool {
+        let total = self
+            .lines
+            .iter()
+            .fold(i128::from(self.opening.amount.value), |total, line| {
+                total + i128::from(line.amount.value)
+            });
+        total == i128::from(self.closing.amount.value)
+            && self
+                .lines
+                .iter()
+                .all(|line| line.amount.symbol == self.opening.amount.symbol)
+            && self.closing.amount.symbol == self.opening.amount.symbol
+    }
+}
+
+/// Splits a message into `(tag, content)` fields, skipping block headers and trailers.
+fn fields(text: &str) -> Vec<(&str, String)> {
+    let mut fields: Vec<(&str, String)> = Vec::new();
+    let mut open = false;
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        let is_tag = |tag: &str| {
+            let bytes = tag.as_bytes();
+            bytes[..2].iter().all(u8::is_ascii_digit)
+                && bytes[2..].iter().all(u8::is_ascii_uppercase)
+        };
+        let tag_end = line
+            .strip_prefix(':')
+            .and_then(|rest| rest.find(':'))
+            .filter(|&end| (2..=3).contains(&end) && is_tag(&line[1..=end]));
+        if let Some(end) = tag_end {
+            fields.push((&line[1..=end], line[end + 2..].to_string()));
+            open = true;
+        } else if line.starts_with('{') || line == "-" || line.starts_with("-}") {
+            open = false;
+        } else if open {
+            if let Some((_, content)) = fields.last_mut() {
+                content.push('\n');
+                content.push_str(line);
+            }
+        }
+    }
+    fields
+}
+
+#[derive(Default)]
+struct Builder {
+    reference: Option<String>,
+    account: Option<String>,
+    sequence: Option<String>,
+    code: Option<String>,
+    opening: Option<Balance>,
+    lines: Vec<StatementLine>,
+    closing: Option<Balance>,
+    available: Option<Balance>,
+    information: Option<String>,
+}
+
+impl Builder {
+    fn build(self) -> Result<Statement, SwiftError> {
+        Ok(Statement {
+            reference: self.reference.ok_or(SwiftError::MissingField("20"))?,
+            account: self.account.ok_or(SwiftError::MissingField("25"))?,
+            sequence: self.sequence,
+            opening: self.opening.ok_or(SwiftError::MissingField("60F"))?,
+            lines: self.lines,
+            closing: self.closing.ok_or(SwiftError::MissingField("62F"))?,
+            available: self.available,
+            information: self.information,
+        })
+    }
+}
+
+/// Parses all MT940 statements in `text`, with or without SWIFT block headers.
+///
+/// Each statement starts with a `:20:` field. Fields this parser does not know, e.g. `:65:`,
+/// are skipped.
+pub fn parse_statements(text: &str) -> Result<Vec<Statement>, SwiftError> {
+    let mut statements = Vec::new();
+    let mut current: Option<Builder> = None;
+    // a `:86:` right after a `:61:` belongs to that line, otherwise to the statement
+    let mut follows_line = false;
+    for (tag, content) in fields(text) {
+        let after_line = follows_line;
+        follows_line = tag == "61";
+        if tag == "20" {
+            if let Some(builder) = current.take() {
+                statements.push(builder.build()?);
+            }
+            current = Some(Builder {
+                reference: Some(content),
+                ..Builder::default()
+            });
+            continue;
+        }
+        let builder = current.as_mut().ok_or(SwiftError::MissingField("20"))?;
+        match tag {
+            "25" => builder.account = Some(content),
+            "28C" => builder.sequence = Some(content),
+            "60F" | "60M" => {
+                builder.code = content.get(7..10).map(str::to_string);
+                builder.opening = Some(parse_balance(&content)?);
+            }
+            "61" => {
+                let code = builder
+                    .code
+                    .as_deref()
+                    .ok_or(SwiftError::MissingField("60F"))?;
+                builder.lines.push(parse_statement_line(&content, code)?);
+            }
+            "86" => match builder.lines.last_mut() {
+                Some(line) if after_line => line.information = Some(content),
+                _ => builder.information = Some(content),
+            },
+            "62F" | "62M" => builder.closing = Some(parse_balance(&content)?),
+            "64" => builder.available = Some(parse_balance(&content)?),
+            _ => {}
+        }
+    }
+    if let Some(builder) = current {
+        statements.push(builder.build()?);
+    }
+    Ok(statements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn euros(value: i64) -> Currency {
+        Currency::from(('€', value))
+    }
+
+    #[test]
+    fn amounts() {
+        assert_eq!(parse_amount("EUR", "1234,56"), Ok(euros(123_456)));
+        assert_eq!(parse_amount("EUR", "1234,5"), Ok(euros(123_450)));
+        assert_eq!(parse_amount("EUR", "1234,"), Ok(euros(123_400)));
+        assert_eq!(parse_amount("EUR", "0,01"), Ok(euros(1)));
+        assert_eq!(parse_amount("EUR", "0,0100"), Ok(euros(1)));
+        assert_eq!(
+            parse_amount("EUR", "99999999999999,"),
+            Ok(euros(9_999_999_999_999_900))
+        );
+
+        for invalid in &[
+            "", ",", ",50", "1234", "1.234,56", "1234.56", "-1,00", "1 234,56", "１,00",
+        ] {
+            assert_eq!(
+                parse_amount("EUR", invalid),
+                Err(SwiftError::InvalidAmount),
+                "{}",
+                invalid
+            );
+        }
+        assert_eq!(
+            parse_amount("EUR", "1234567890123456,"),
+            Err(SwiftError::InvalidAmount)
+        );
+        assert_eq!(parse_amount("EUR", "0,001"), Err(SwiftError::PrecisionLoss));
+        assert_eq!(
+            parse_amount("XYZ", "1,00"),
+            Err(SwiftError::UnknownCurrency)
+        );
+        assert_eq!(
+            parse_amount("eur", "1,00"),
+            Err(SwiftError::UnknownCurrency)
+        );
+    }
+
+    #[test]
+    fn signs() {
+        assert_eq!(parse_signed("C", "EUR", "1,00"), Ok(euros(100)));
+        assert_eq!(parse_signed("D", "EUR", "1,00"), Ok(euros(-100)));
+        assert_eq!(parse_signed("RC", "EUR", "1,00"), Ok(euros(-100)));
+        assert_eq!(parse_signed("RD", "EUR", "1,00"), Ok(euros(100)));
+        assert_eq!(
+            parse_signed("X", "EUR", "1,00"),
+            Err(SwiftError::InvalidMark)
+        );
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(format_amount(euros(123_456)), Ok("1234,56".to_string()));
+        assert_eq!(format_amount(euros(5)), Ok("0,05".to_string()));
+        assert_eq!(format_amount(euros(-5)), Err(SwiftError::Negative));
+        assert_eq!(format_amount(euros(i64::MAX)), Err(SwiftError::TooLarge));
+        assert_eq!(
+            format_signed(euros(-5)),
+            Ok((Mark::Debit, "0,05".to_string()))
+        );
+        assert_eq!(
+            format_signed(euros(0)),
+            Ok((Mark::Credit, "0,00".to_string()))
+        );
+        assert_eq!(format_signed(euros(i64::MIN)), Err(SwiftError::TooLarge));
+        assert_eq!(
+            format_currency_amount(Currency::from(('$', 99))),
+            Ok("USD0,99".to_string())
+        );
+        assert_eq!(
+            format_currency_amount(Currency::from(99)),
+            Err(SwiftError::UnknownCurrency)
+        );
+
+        let largest = euros(99_999_999_999_999);
+        let (mark, amount) = format_signed(-largest).unwrap();
+        assert_eq!(parse_signed(mark.code(), "EUR", &amount), Ok(-largest));
+    }
+
+    #[test]
+    fn field_32a() {
+        let date = Date::new(2024, 2, 29).unwrap();
+        let field = format_32a(date, Currency::from(('£', 100_000))).unwrap();
+        assert_eq!(field, "240229GBP1000,00");
+        assert_eq!(
+            parse_32a(&field),
+            Ok((date, Currency::from(('£', 100_000))))
+        );
+        assert_eq!(parse_32a("230229GBP1,00"), Err(SwiftError::InvalidDate));
+        assert_eq!(parse_32a("2302"), Err(SwiftError::InvalidDate));
+        assert_eq!(parse_32a("230228G"), Err(SwiftError::UnknownCurrency));
+        assert_eq!(
+            format_32a(Date::new(1999, 12, 31).unwrap(), euros(1)),
+            Err(SwiftError::InvalidDate)
+        );
+    }
+
+    #[test]
+    fn statement_line() {
+        let line =
+            parse_statement_line("2301160116D250,50NTRFNONREF//B123456789\nRENT", "EUR").unwrap();
+        assert_eq!(line.value_date, Date::new(2023, 1, 16).unwrap());
+        assert_eq!(line.entry_date, Date::new(2023, 1, 16));
+        assert_eq!(line.mark, Mark::Debit);
+        assert_eq!(line.funds_code, None);
+        assert_eq!(line.amount, euros(-25_050));
+        assert_eq!(line.transaction_type, "NTRF");
+        assert_eq!(line.customer_reference, "NONREF");
+        assert_eq!(line.bank_reference.as_deref(), Some("B123456789"));
+        assert_eq!(line.supplementary_details.as_deref(), Some("RENT"));
+
+        let line = parse_statement_line("230102RD5,S103NONREF", "EUR").unwrap();
+        assert_eq!(line.entry_date, None);
+        assert_eq!(line.mark, Mark::ReversalOfDebit);
+        assert_eq!(line.amount, euros(500));
+        assert_eq!(line.transaction_type, "S103");
+
+        // booked in the year before the value date
+        let line = parse_statement_line("2301021231C5,NTRFNONREF", "EUR").unwrap();
+        assert_eq!(line.entry_date, Date::new(2022, 12, 31));
+        let line = parse_statement_line("2212310102C5,NTRFNONREF", "EUR").unwrap();
+        assert_eq!(line.entry_date, Date::new(2023, 1, 2));
+
+        for invalid in &[
+            "230116X5,NTRFNONREF",
+            "230116C5,NTRF",
+            "230116C5,NT",
+            "230116C5,00",
+        ] {
+            assert!(parse_statement_line(invalid, "EUR").is_err(), "{}", invalid);
+        }
+        assert_eq!(
+            parse_statement_line("230116CNTRFNONREF", "EUR"),
+            Err(SwiftError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn missing_fields() {
+        assert_eq!(
+            parse_statements(":25:ACCOUNT\n"),
+            Err(SwiftError::MissingField("20"))
+        );
+        assert_eq!(
+            parse_statements(":20:REF\n:25:ACCOUNT\n:60F:C230101EUR1,00\n"),
+            Err(SwiftError::MissingField("62F"))
+        );
+        assert_eq!(
+            parse_statements(":20:REF\n:25:ACCOUNT\n:61:230116C5,NTRFNONREF\n"),
+            Err(SwiftError::MissingField("60F"))
+        );
+        assert_eq!(parse_statements(""), Ok(vec![]));
+    }
+}
@@ -0,0 +1,302 @@
+//! Prices per unit with more precision than the smallest unit of the currency.
+//!
+//! Fuel at €1.759 per litre or compute at $0.0000166 per GB-second cannot be held by a
+//! [`Currency`]. A [`UnitPrice`] keeps the exact amount and only rounds once, when it is
+//! multiplied by a quantity.
+//!
+//! # Examples
+//! ```
+//! use claude::rounding::RoundingMode;
+//! use claude::unit_price::UnitPrice;
+//! use claude::Currency;
+//!
+//! let fuel = UnitPrice::new(Some('€'), "1.759".parse().unwrap());
+//! assert_eq!(fuel.to_string(), "€1.759");
+//! assert_eq!(fuel.postfix().to_string(), "1,759€");
+//!
+//! let litres = "42.37".parse().unwrap();
+//! assert_eq!(fuel.total(litres, RoundingMode::HalfUp), Some(Currency::from(('€', 7453))));
+//! ```
+
+use std::fmt;
+
+use crate::decimal::Decimal;
+use crate::display::write_amount;
+use crate::rounding::{div_round, RoundingMode};
+use crate::{Currency, Symbol};
+
+/// An exact price per unit, e.g. `$0.0000166`, in whole units of the currency.
+///
+/// Unlike [`Currency`] the amount is not limited to hundredths. Prices compare by value,
+/// `€1.50 == €1.500`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UnitPrice {
+    symbol: Option<Symbol>,
+    amount: Decimal,
+}
+
+impl UnitPrice {
+    /// Creates a price of `amount` whole units per unit, `Decimal::new(1759, 3)` is `1.759`.
+    pub fn new(symbol: Option<Symbol>, amount: Decimal) -> UnitPrice {
+        UnitPrice { symbol, amount }
+    }
+
+    /// The currency symbol.
+    pub fn symbol(&self) -> Option<Symbol> {
+        self.symbol
+    }
+
+    /// The price in whole units of the currency.
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    /// The price of `quantity` units, computed exactly and rounded once to the smallest unit with
+    /// `mode`.
+    ///
+    /// Returns `None` if the result does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use claude::rounding::RoundingMode;
+    /// use claude::unit_price::UnitPrice;
+    /// use claude::Currency;
+    ///
+    /// let lambda = UnitPrice::new(Some('$'), "0.0000166667".parse().unwrap());
+    /// let seconds = 1_200_000.into();
+    /// assert_eq!(lambda.total(seconds, RoundingMode::HalfUp), Some(Currency::from(('$', 2000))));
+    /// assert_eq!(lambda.total(seconds, RoundingMode::Up), Some(Currency::from(('$', 2001))));
+    /// ```
+    pub fn total(&self, quantity: Decimal, mode: RoundingMode) -> Option<Currency> {
+        // trailing zeros only make the mantissas larger, `1.000 × 2.0` is `1 × 2`
+        let (amount, quantity) = (self.amount.normalized(), quantity.normalized());
+        let mantissa = amount
+            .mantissa()
+            .checked_mul(quantity.mantissa())?
+            .checked_mul(100)?;
+        let value = match 10i128.checked_pow(amount.scale() + quantity.scale()) {
+            Some(divisor) => div_round(mantissa, divisor, mode),
+            // the divisor exceeds every mantissa, so the exact result lies strictly between zero
+            // and ±0.2 and only its sign matters for rounding
+            None => div_round(mantissa.signum(), 10, mode),
+        };
+        if value < i128::from(i64::MIN) || value > i128::from(i64::MAX) {
+            return None;
+        }
+        Some(Currency {
+            symbol: self.symbol,
+            value: value as i64,
+        })
+    }
+
+    /// The price of a single unit rounded to the smallest unit with `mode`.
+    ///
+    /// Returns `None` if the result does not fit.
+    pub fn round(&self, mode: RoundingMode) -> Option<Currency> {
+        self.total(Decimal::from(1), mode)
+    }
+
+    /// Returns an object that implements `Display` with the symbol at the end and a decimal
+    /// comma, like [`Currency::postfix`].
+    pub fn postfix(&self) -> Postfix<'_> {
+        Postfix { price: self }
+    }
+
+    /// Formats the digits with the formatter's precision, or all fractional digits but at least
+    /// two, rounding half away from zero.
+    ///
+    /// Returns the digits and whether the rounded amount is zero.
+    fn digits(&self, precision: Option<usize>, separator: char) -> (String, bool) {
+        let scale = match precision {
+            Some(precision) => precision as u32,
+            None => self.amount.scale().max(2),
+        };
+        let digits = match self.amount.rescale(scale, RoundingMode::HalfUp) {
+            Some(rounded) => Decimal::new(rounded.mantissa().abs(), scale).to_string(),
+            // too many digits to fit, print the exact amount instead
+            None => Decimal::new(self.amount.mantissa().abs(), self.amount.scale()).to_string(),
+        };
+        let is_zero = digits.bytes().all(|b| b == b'0' || b == b'.');
+        (digits.replace('.', &separator.to_string()), is_zero)
+    }
+}
+
+/// Converts exactly, `$12.34` is a price of `12.34`.
+impl From<Currency> for UnitPrice {
+    fn from(money: Currency) -> UnitPrice {
+        UnitPrice {
+            symbol: money.symbol,
+            amount: Decimal::new(i128::from(money.value), 2),
+        }
+    }
+}
+
+/// Implements `Display` for a [`UnitPrice`] with the currency symbol at the end.
+pub struct Postfix<'a> {
+    price: &'a UnitPrice,
+}
+
+/// Displays the price with the symbol at the front like [`Currency`], honouring the same
+/// formatter flags. Without a precision all fractional digits are printed, at least two.
+///
+/// # Examples
+/// ```
+/// use claude::unit_price::UnitPrice;
+///
+/// let price = UnitPrice::new(Some('$'), "0.0000166".parse().unwrap());
+/// assert_eq!(price.to_string(), "$0.0000166");
+/// assert_eq!(format!("{:.4}", price), "$0.0000");
+/// assert_eq!(format!("{:>8}", UnitPrice::new(Some('$'), "-1.5".parse().unwrap())), "  -$1.50");
+/// ```
+impl fmt::Display for UnitPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (digits, is_zero) = self.digits(f.precision(), '.');
+        let mut symbol = [0; 4];
+        let prefix = match self.symbol {
+            Some(symbol_char) => &*symbol_char.encode_utf8(&mut symbol),
+            None => "",
+        };
+        write_amount(
+            f,
+            self.amount.is_negative() && !is_zero,
+            prefix,
+            &digits,
+            "",
+        )
+    }
+}
+
+impl<'a> fmt::Display for Postfix<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (digits, is_zero) = self.price.digits(f.precision(), ',');
+        let mut symbol = [0; 4];
+        let suffix = match self.price.symbol {
+            Some(symbol_char) => &*symbol_char.encode_utf8(&mut symbol),
+            None => "",
+        };
+        write_amount(
+            f,
+            self.price.amount.is_negative() && !is_zero,
+            "",
+            &digits,
+            suffix,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn euros(amount: &str) -> UnitPrice {
+        UnitPrice::new(Some('€'), dec(amount))
+    }
+
+    #[test]
+    fn total_rounds_once() {
+        // 3 × 0.333 = 0.999, rounding each unit first would give 0.99
+        let price = euros("0.333");
+        assert_eq!(
+            price.total(dec("3"), RoundingMode::HalfUp),
+            Some(Currency::from(('€', 100)))
+        );
+        assert_eq!(
+            price.total(dec("3"), RoundingMode::Down),
+            Some(Currency::from(('€', 99)))
+        );
+        assert_eq!(
+            euros("-1.005").total(dec("1"), RoundingMode::HalfUp),
+            Some(Currency::from(('€', -101)))
+        );
+        assert_eq!(
+            euros("1.759").total(dec("0"), RoundingMode::Up),
+            Some(Currency::from(('€', 0)))
+        );
+    }
+
+    #[test]
+    fn tiny_products() {
+        let tiny = dec("0.00000000000000000001");
+        let price = UnitPrice::new(Some('$'), tiny);
+        let dollars = |value| Some(Currency::from(('$', value)));
+        assert_eq!(price.total(tiny, RoundingMode::HalfUp), dollars(0));
+        assert_eq!(price.total(tiny, RoundingMode::Up), dollars(1));
+        assert_eq!(
+            price.total(dec("-1.0000000000000000000"), RoundingMode::Floor),
+            dollars(-1)
+        );
+        assert_eq!(
+            price.total(dec("-1.0000000000000000000"), RoundingMode::Ceiling),
+            dollars(0)
+        );
+        assert_eq!(
+            price.total(Decimal::new(0, 28), RoundingMode::Up),
+            dollars(0)
+        );
+    }
+
+    #[test]
+    fn trailing_zeros() {
+        let one = dec("1.0000000000000000000");
+        assert_eq!(
+            UnitPrice::new(Some('€'), one).total(one, RoundingMode::HalfUp),
+            Some(Currency::from(('€', 100)))
+        );
+        assert_eq!(
+            euros("1.7590000000000000000")
+                .total(dec("42.3700000000000000000"), RoundingMode::HalfUp),
+            Some(Currency::from(('€', 7453)))
+        );
+    }
+
+    #[test]
+    fn round() {
+        assert_eq!(
+            euros("1.759").round(RoundingMode::HalfUp),
+            Some(Currency::from(('€', 176)))
+        );
+        assert_eq!(
+            euros("1.759").round(RoundingMode::Down),
+            Some(Currency::from(('€', 175)))
+        );
+        let money = Currency::from(('€', -1234));
+        assert_eq!(UnitPrice::from(money).round(RoundingMode::Up), Some(money));
+    }
+
+    #[test]
+    fn overflow() {
+        let price = UnitPrice::new(None, Decimal::from(i64::MAX));
+        assert_eq!(price.total(dec("1"), RoundingMode::HalfUp), None);
+        assert_eq!(
+            price.total(dec("0.01"), RoundingMode::HalfUp),
+            Some(Currency {
+                symbol: None,
+                value: i64::MAX
+            })
+        );
+        let huge = UnitPrice::new(None, Decimal::new(i128::MAX, 0));
+        assert_eq!(huge.total(dec("2"), RoundingMode::HalfUp), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(euros("1.759").to_string(), "€1.759");
+        assert_eq!(euros("2").to_string(), "€2.00");
+        assert_eq!(euros("1.5").postfix().to_string(), "1,50€");
+        assert_eq!(format!("{:.2}", euros("1.755")), "€1.76");
+        assert_eq!(format!("{:.1}", euros("-0.04")), "€0.0");
+        assert_eq!(format!("{:+.0}", euros("12.5").postfix()), "+13€");
+        assert_eq!(format!("{:010}", euros("-1.759")), "-€0001.759");
+        assert_eq!(UnitPrice::new(None, dec("0.0001")).to_string(), "0.0001");
+    }
+
+    #[test]
+    fn compares_by_value() {
+        assert_eq!(euros("1.50"), euros("1.5"));
+        assert_ne!(euros("1.5"), UnitPrice::new(Some('$'), dec("1.5")));
+    }
+}
@@ -0,0 +1,404 @@
+//! Spelling out amounts in words, as required on cheques and payment orders.
+
+use std::fmt;
+
+use crate::locale::Language;
+use crate::Currency;
+
+/// How the minor units are written.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WordsStyle {
+    /// Minor units are spelled out as well: "forty-five cents"
+    Spelled,
+    /// Minor units are written as a fraction of a hundred, as on cheques: "and 45/100"
+    Cheque,
+}
+
+/// Implements `Display` by spelling out the amount in words.
+///
+/// Unit names are taken from the [`CurrencyInfo`](crate::info::CurrencyInfo) of the symbol, a
+/// currency without known metadata is spelled without unit names.
+pub struct Words<'a> {
+    money: &'a Currency,
+    language: Language,
+    style: WordsStyle,
+}
+
+impl<'a> Words<'a> {
+    pub(crate) fn new(money: &'a Currency, language: Language) -> Words<'a> {
+        Words {
+            money,
+            language,
+            style: WordsStyle::Spelled,
+        }
+    }
+
+    /// Writes the minor units as a fraction, as on cheques.
+    pub fn cheque(self) -> Words<'a> {
+        self.style(WordsStyle::Cheque)
+    }
+
+    /// Sets how the minor units are written.
+    pub fn style(mut self, style: WordsStyle) -> Words<'a> {
+        self.style = style;
+        self
+    }
+}
+
+/// Spells out the amount.
+///
+/// # Examples
+/// ```
+/// use claude::{locale::Language, Currency};
+///
+/// let cheque = Currency::from(('$', 12345));
+/// assert_eq!(
+///     cheque.to_words(Language::English).to_string(),
+///     "one hundred twenty-three dollars and forty-five cents"
+/// );
+/// assert_eq!(
+///     cheque.to_words(Language::English).cheque().to_string(),
+///     "one hundred twenty-three dollars and 45/100"
+/// );
+/// assert_eq!(
+///     Currency::from(('€', 2101)).to_words(Language::German).to_string(),
+///     "einundzwanzig Euro und ein Cent"
+/// );
+/// ```
+impl<'a> fmt::Display for Words<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (minus, and, hundredth, hundredths) = match self.language {
+            Language::English => ("minus", "and", "hundredth", "hundredths"),
+            Language::German => ("minus", "und", "Hundertstel", "Hundertstel"),
+        };
+        let spell = |n: u64, before_noun: bool| match self.language {
+            Language::English => english(n),
+            Language::German if before_noun => german_attributive(n),
+            Language::German => german(n),
+        };
+
+        let abs = self.money.value.unsigned_abs();
+        let (major, minor) = (abs / 100, abs % 100);
+        let names = self.money.info().map(|info| info.unit_names(self.language));
+
+        if self.money.value < 0 {
+            write!(f, "{} ", minus)?;
+        }
+
+        let write_major = |f: &mut fmt::Formatter<'_>| match names {
+            Some(names) => write!(f, "{} {}", spell(major, true), names.major.for_count(major)),
+            None => write!(f, "{}", spell(major, false)),
+        };
+
+        match (self.style, names) {
+            (WordsStyle::Cheque, _) => {
+                write_major(f)?;
+                write!(f, " {} {:02}/100", and, minor)
+            }
+            (WordsStyle::Spelled, _) if minor == 0 => write_major(f),
+            (WordsStyle::Spelled, Some(names)) => {
+                if major > 0 {
+                    write_major(f)?;
+                    write!(f, " {} ", and)?;
+                }
+                write!(f, "{} {}", spell(minor, true), names.minor.for_count(minor))
+            }
+            (WordsStyle::Spelled, None) => {
+                let fraction = if minor == 1 { hundredth } else { hundredths };
+                write_major(f)?;
+                write!(f, " {} {} {}", and, spell(minor, true), fraction)
+            }
+        }
+    }
+}
+
+const ENGLISH_ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const ENGLISH_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const ENGLISH_SCALES: [(u64, &str); 5] = [
+    (1_000_000_000_000_000, "quadrillion"),
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+fn english(n: u64) -> String {
+    if n == 0 {
+        return ENGLISH_ONES[0].into();
+    }
+
+    let mut parts = Vec::new();
+    let mut rest = n;
+    for &(scale, name) in ENGLISH_SCALES.iter() {
+        if rest >= scale {
+            parts.push(format!("{} {}", english_below_thousand(rest / scale), name));
+            rest %= scale;
+        }
+    }
+    if rest > 0 {
+        parts.push(english_below_thousand(rest));
+    }
+    parts.join(" ")
+}
+
+fn english_below_thousand(n: u64) -> String {
+    let (hundreds, rest) = (n / 100, (n % 100) as usize);
+    let tens = match rest {
+        0 => String::new(),
+        1..=19 => ENGLISH_ONES[rest].into(),
+        _ if rest % 10 == 0 => ENGLISH_TENS[rest / 10].into(),
+        _ => format!("{}-{}", ENGLISH_TENS[rest / 10], ENGLISH_ONES[rest % 10]),
+    };
+    match (hundreds, tens.is_empty()) {
+        (0, _) => tens,
+        (h, true) => format!("{} hundred", ENGLISH_ONES[h as usize]),
+        (h, false) => format!("{} hundred {}", ENGLISH_ONES[h as usize], tens),
+    }
+}
+
+const GERMAN_ONES: [&str; 20] = [
+    "null",
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+];
+
+const GERMAN_TENS: [&str; 10] = [
+    "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig",
+];
+
+const GERMAN_SCALES: [(u64, &str, &str); 4] = [
+    (1_000_000_000_000_000, "Billiarde", "Billiarden"),
+    (1_000_000_000_000, "Billion", "Billionen"),
+    (1_000_000_000, "Milliarde", "Milliarden"),
+    (1_000_000, "Million", "Millionen"),
+];
+
+fn german(n: u64) -> String {
+    if n == 0 {
+        return GERMAN_ONES[0].into();
+    }
+
+    let mut parts = Vec::new();
+    let mut rest = n;
+    for &(scale, singular, plural) in GERMAN_SCALES.iter() {
+        if rest >= scale {
+            let count = rest / scale;
+            // the scale words are feminine: "eine Million", "einhunderteine Millionen"
+            let mut word = german_below_thousand(count, false);
+            if count % 100 == 1 {
+                word.push('e');
+            }
+            let name = if count == 1 { singular } else { plural };
+            parts.push(format!("{} {}", word, name));
+            rest %= scale;
+        }
+    }
+    if rest > 0 {
+        let (thousands, below) = (rest / 1000, rest % 1000);
+        let mut word = String::new();
+        if thousands > 0 {
+            word.push_str(&german_below_thousand(thousands, false));
+            word.push_str("tausend");
+        }
+        if below > 0 {
+            word.push_str(&german_below_thousand(below, true));
+        }
+        parts.push(word);
+    }
+    parts.join(" ")
+}
+
+/// Spells `n` as it is written before a noun, where a final "eins" becomes "ein": "einhundertein
+/// Euro".
+fn german_attributive(n: u64) -> String {
+    let mut word = german(n);
+    if word.ends_with("eins") {
+        word.pop();
+    }
+    word
+}
+
+/// Spells `n < 1000` as one word, `last` decides between "eins" and the prefix form "ein".
+fn german_below_thousand(n: u64, last: bool) -> String {
+    let (hundreds, rest) = (n / 100, (n % 100) as usize);
+    let mut word = String::new();
+    if hundreds > 0 {
+        word.push_str(german_prefix(hundreds as usize));
+        word.push_str("hundert");
+    }
+    match rest {
+        0 => {}
+        1 if last => word.push_str(GERMAN_ONES[1]),
+        1..=19 => word.push_str(german_prefix(rest)),
+        _ if rest % 10 == 0 => word.push_str(GERMAN_TENS[rest / 10]),
+        _ => {
+            word.push_str(german_prefix(rest % 10));
+            word.push_str("und");
+            word.push_str(GERMAN_TENS[rest / 10]);
+        }
+    }
+    word
+}
+
+fn german_prefix(n: usize) -> &'static str {
+    match n {
+        1 => "ein",
+        _ => GERMAN_ONES[n],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_numbers() {
+        assert_eq!(english(0), "zero");
+        assert_eq!(english(13), "thirteen");
+        assert_eq!(english(40), "forty");
+        assert_eq!(english(123), "one hundred twenty-three");
+        assert_eq!(english(1_000_001), "one million one");
+        assert_eq!(
+            english(92_233_720_368_547_758),
+            "ninety-two quadrillion two hundred thirty-three trillion seven hundred twenty billion \
+             three hundred sixty-eight million five hundred forty-seven thousand seven hundred \
+             fifty-eight"
+        );
+    }
+
+    #[test]
+    fn german_numbers() {
+        assert_eq!(german(0), "null");
+        assert_eq!(german(1), "eins");
+        assert_eq!(german(17), "siebzehn");
+        assert_eq!(german(21), "einundzwanzig");
+        assert_eq!(german(101), "einhunderteins");
+        assert_eq!(german(1000), "eintausend");
+        assert_eq!(
+            german(123_456),
+            "einhundertdreiundzwanzigtausendvierhundertsechsundfünfzig"
+        );
+        assert_eq!(german(1_000_000), "eine Million");
+        assert_eq!(german(2_001_000), "zwei Millionen eintausend");
+        assert_eq!(german(101_000_000), "einhunderteine Millionen");
+        assert_eq!(german(311_000_000_000), "dreihundertelf Milliarden");
+        assert_eq!(german(21_000_000), "einundzwanzig Millionen");
+    }
+
+    #[test]
+    fn plurals_and_minor_units() {
+        let words = |symbol, value, language| {
+            Currency::from((symbol, value))
+                .to_words(language)
+                .to_string()
+        };
+        assert_eq!(words('$', 100, Language::English), "one dollar");
+        assert_eq!(words('$', 1, Language::English), "one cent");
+        assert_eq!(
+            words('£', 202, Language::English),
+            "two pounds and two pence"
+        );
+        assert_eq!(
+            words('£', 101, Language::English),
+            "one pound and one penny"
+        );
+        assert_eq!(words('€', 0, Language::English), "zero euros");
+        assert_eq!(
+            words('₹', -250, Language::German),
+            "minus zwei Rupien und fünfzig Paise"
+        );
+        assert_eq!(words('€', 100, Language::German), "ein Euro");
+        assert_eq!(words('€', 10100, Language::German), "einhundertein Euro");
+        assert_eq!(
+            words('€', 100_000_101, Language::German),
+            "eine Million ein Euro und ein Cent"
+        );
+        assert_eq!(
+            words('€', 2101, Language::German),
+            "einundzwanzig Euro und ein Cent"
+        );
+    }
+
+    #[test]
+    fn cheque_style() {
+        let cheque = |money: Currency, language| money.to_words(language).cheque().to_string();
+        assert_eq!(
+            cheque(Currency::from(('€', 700)), Language::German),
+            "sieben Euro und 00/100"
+        );
+        assert_eq!(
+            cheque(Currency::from(('$', 5)), Language::English),
+            "zero dollars and 05/100"
+        );
+    }
+
+    #[test]
+    fn without_metadata() {
+        let money = Currency::from(12345);
+        assert_eq!(
+            money.to_words(Language::English).to_string(),
+            "one hundred twenty-three and forty-five hundredths"
+        );
+        assert_eq!(
+            money.to_words(Language::German).cheque().to_string(),
+            "einhundertdreiundzwanzig und 45/100"
+        );
+        let hundredth = Currency::from(10101);
+        assert_eq!(
+            hundredth.to_words(Language::English).to_string(),
+            "one hundred one and one hundredth"
+        );
+        assert_eq!(
+            hundredth.to_words(Language::German).to_string(),
+            "einhunderteins und ein Hundertstel"
+        );
+        assert_eq!(
+            Currency::from(('₿', 102))
+                .to_words(Language::English)
+                .to_string(),
+            "one and two hundredths"
+        );
+    }
+}
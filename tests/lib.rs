@@ -1,3 +1,5 @@
+use claude::amount::Amount;
+use claude::quantity::Quantity;
 use claude::Currency;
 use std::cmp::Ordering;
 
@@ -21,6 +23,12 @@ fn taxes() {
     assert_eq!(1.19 * a, b);
     assert_eq!(a * 1.1901, b);
     assert_eq!(1.1901 * a, b);
+
+    let rate = |s: &str| s.parse::<Quantity>().unwrap();
+    assert_eq!(a * rate("1.19"), b);
+    assert_eq!(rate("1.19") * a, b);
+    assert_eq!(a * rate("1.1901"), b);
+    assert_eq!(rate("1.1901") * a, b);
 }
 
 #[test]
@@ -282,8 +290,9 @@ fn add_two_defaults() {
 
 #[test]
 fn add_default_to_symbolled() {
+    // a default amount without a symbol is an `Amount`, a bare `Currency` no longer adapts
     assert_eq!(
-        Currency::default()
+        Amount::default()
             + Currency {
                 symbol: Some('€'),
                 value: 1,
@@ -294,3 +303,13 @@ fn add_default_to_symbolled() {
         }
     );
 }
+
+#[test]
+#[should_panic(expected = "different types of currency")]
+fn add_bare_currency_to_symbolled_panics() {
+    let _ = Currency::default()
+        + Currency {
+            symbol: Some('€'),
+            value: 1,
+        };
+}
@@ -0,0 +1,107 @@
+use claude::Currency;
+use proptest::prelude::*;
+
+fn symbol() -> impl Strategy<Value = Option<char>> {
+    prop::option::of(prop::sample::select(vec!['$', '€', '£', '¥', '₹']))
+}
+
+fn value() -> impl Strategy<Value = i64> {
+    prop_oneof![
+        8 => any::<i64>(),
+        4 => -100_000i64..100_000,
+        1 => Just(i64::MIN),
+        1 => Just(i64::MAX),
+    ]
+}
+
+fn currency() -> impl Strategy<Value = Currency> {
+    (symbol(), value()).prop_map(|(symbol, value)| Currency { symbol, value })
+}
+
+proptest! {
+    #[test]
+    fn add_then_sub_is_identity(symbol in symbol(), a in i64::MIN / 2..i64::MAX / 2, b in i64::MIN / 2..i64::MAX / 2) {
+        let a = Currency { symbol, value: a };
+        let b = Currency { symbol, value: b };
+        prop_assert_eq!(a + b - b, a);
+    }
+
+    #[test]
+    fn wrapping_add_then_sub_is_identity(a in currency(), b in value()) {
+        let b = Currency { symbol: a.symbol, value: b };
+        prop_assert_eq!(a.wrapping_add(b).wrapping_sub(b), a);
+    }
+
+    #[test]
+    fn split_parts_sum_to_original(money in currency(), parts in 1u32..100) {
+        let split = money.split(parts);
+        prop_assert_eq!(split.len(), parts as usize);
+        let sum: i128 = split.iter().map(|part| i128::from(part.value)).sum();
+        prop_assert_eq!(sum, i128::from(money.value));
+
+        let min = split.iter().map(|part| part.value).min().unwrap();
+        let max = split.iter().map(|part| part.value).max().unwrap();
+        prop_assert!(max - min <= 1);
+        prop_assert!(split.iter().all(|part| part.symbol == money.symbol));
+    }
+
+    #[test]
+    fn swift_input_does_not_panic(s in "[:\r\n0-9CDR,A-Z/{}é-]{0,80}") {
+        let _ = claude::swift::parse_statements(&s);
+        let _ = claude::swift::parse_statement_line(&s, "EUR");
+        let _ = claude::swift::parse_32a(&s);
+    }
+
+    #[test]
+    fn swift_amount_round_trip(value in 0i64..100_000_000_000_000) {
+        let money = Currency::from(('€', value));
+        let (mark, amount) = claude::swift::format_signed(-money).unwrap();
+        prop_assert_eq!(claude::swift::parse_signed(mark.code(), "EUR", &amount), Ok(-money));
+    }
+}
+
+#[cfg(feature = "parsing")]
+mod parsing {
+    use super::*;
+    use claude::locale::Locale;
+
+    proptest! {
+        #[test]
+        fn parse_display(money in currency()) {
+            prop_assert_eq!(money.to_string().parse::<Currency>(), Ok(money));
+        }
+
+        #[test]
+        fn parse_postfix(money in currency()) {
+            prop_assert_eq!(money.postfix().to_string().parse::<Currency>(), Ok(money));
+        }
+
+        #[test]
+        fn from_string_display(money in currency()) {
+            prop_assert_eq!(Currency::from_string(&money.to_string()), Some(money));
+        }
+
+        #[test]
+        fn parse_with_locale_display(money in currency()) {
+            prop_assert_eq!(Currency::from_str_with_locale(&money.to_string(), Locale::EnUs), Ok(money));
+        }
+
+        #[test]
+        fn arbitrary_input_does_not_panic(s in "\\PC*") {
+            let _ = Currency::from_string(&s);
+            let _ = s.parse::<Currency>();
+            for &locale in &[Locale::EnUs, Locale::EnGb, Locale::EnIn, Locale::DeDe, Locale::DeCh] {
+                let _ = Currency::from_str_with_locale(&s, locale);
+            }
+        }
+
+        #[test]
+        fn amount_like_input_does_not_panic(s in "[-+ $€¥₹£\u{A0}\u{202F}٫٬０-９0-9.,']{0,40}") {
+            let _ = Currency::from_string(&s);
+            let _ = s.parse::<Currency>();
+            for &locale in &[Locale::EnUs, Locale::EnGb, Locale::EnIn, Locale::DeDe, Locale::DeCh] {
+                let _ = Currency::from_str_with_locale(&s, locale);
+            }
+        }
+    }
+}
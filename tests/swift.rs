@@ -0,0 +1,125 @@
+use claude::swift::{self, Date, Mark};
+use claude::Currency;
+
+const SINGLE: &str = include_str!("data/mt940_single.sta");
+const MULTI: &str = include_str!("data/mt940_multi.sta");
+const STATEMENT_INFO: &str = include_str!("data/mt940_statement_info.sta");
+const MT103: &str = include_str!("data/mt103.txt");
+
+fn euros(value: i64) -> Currency {
+    Currency::from(('€', value))
+}
+
+fn pounds(value: i64) -> Currency {
+    Currency::from(('£', value))
+}
+
+#[test]
+fn single_statement_with_blocks() {
+    let statements = swift::parse_statements(SINGLE).unwrap();
+    assert_eq!(statements.len(), 1);
+    let statement = &statements[0];
+
+    assert_eq!(statement.reference, "STMT230116");
+    assert_eq!(statement.account, "10020030/1234567890");
+    assert_eq!(statement.sequence.as_deref(), Some("12/1"));
+    assert_eq!(statement.opening.date, Date::new(2023, 1, 13).unwrap());
+    assert_eq!(statement.opening.amount, euros(100_000));
+    assert_eq!(statement.closing.amount, euros(224_951));
+    assert_eq!(
+        statement.available.as_ref().map(|balance| balance.amount),
+        Some(euros(224_951))
+    );
+
+    let amounts: Vec<Currency> = statement.lines.iter().map(|line| line.amount).collect();
+    assert_eq!(amounts, vec![euros(-25_050), euros(150_000), euros(1)]);
+
+    let rent = &statement.lines[0];
+    assert_eq!(rent.mark, Mark::Debit);
+    assert_eq!(rent.bank_reference.as_deref(), Some("B123456789"));
+    assert_eq!(
+        rent.information.as_deref(),
+        Some("166?00SEPA CREDIT TRANSFER?20RENT JANUARY\n?21FLAT 4B?32LANDLORD GMBH")
+    );
+    let salary = &statement.lines[1];
+    assert_eq!(salary.funds_code, Some('R'));
+    assert_eq!(salary.customer_reference, "REF-0042");
+    assert_eq!(salary.information.as_deref(), Some("SALARY"));
+    assert_eq!(statement.lines[2].entry_date, None);
+
+    assert!(statement.is_balanced());
+}
+
+#[test]
+fn multiple_statements() {
+    let statements = swift::parse_statements(MULTI).unwrap();
+    assert_eq!(statements.len(), 2);
+
+    let first = &statements[0];
+    assert_eq!(first.opening.amount, pounds(-1234));
+    assert_eq!(
+        first.lines[0].supplementary_details.as_deref(),
+        Some("SUPPLEMENTARY DETAILS")
+    );
+    assert_eq!(first.lines[1].mark, Mark::ReversalOfDebit);
+    assert_eq!(first.lines[1].amount, pounds(500));
+    assert_eq!(
+        first.lines[1].information.as_deref(),
+        Some("REVERSAL OF FEE")
+    );
+    assert_eq!(first.information.as_deref(), Some("INTERIM STATEMENT"));
+    assert!(first.is_balanced());
+
+    let second = &statements[1];
+    assert_eq!(second.opening, first.closing);
+    assert_eq!(second.lines[0].mark, Mark::ReversalOfCredit);
+    assert_eq!(second.lines[0].amount, pounds(-9266));
+    assert_eq!(second.lines[0].entry_date, Date::new(2022, 12, 31));
+    assert_eq!(second.closing.amount, pounds(0));
+    assert!(second.is_balanced());
+}
+
+#[test]
+fn statement_information_after_closing_balance() {
+    let statements = swift::parse_statements(STATEMENT_INFO).unwrap();
+    let statement = &statements[0];
+    assert_eq!(
+        statement.lines[0].information.as_deref(),
+        Some("DIRECT DEBIT ELECTRICITY")
+    );
+    assert_eq!(statement.lines[1].amount, euros(-499));
+    assert_eq!(statement.lines[1].information, None);
+    assert_eq!(
+        statement.information.as_deref(),
+        Some("ACCOUNT STATEMENT MARCH")
+    );
+    assert!(statement.is_balanced());
+}
+
+#[test]
+fn unbalanced_statement() {
+    let tampered = SINGLE.replace(":62F:C230116EUR2249,51", ":62F:C230116EUR2249,50");
+    let statements = swift::parse_statements(&tampered).unwrap();
+    assert!(!statements[0].is_balanced());
+}
+
+#[test]
+fn malformed_statement() {
+    let tampered = SINGLE.replace("D250,50NTRF", "D250.50NTRF");
+    assert!(swift::parse_statements(&tampered).is_err());
+}
+
+#[test]
+fn mt103_amounts() {
+    let field = |tag: &str| {
+        MT103
+            .lines()
+            .find_map(|line| line.strip_prefix(tag))
+            .unwrap()
+    };
+    let (date, amount) = swift::parse_32a(field(":32A:")).unwrap();
+    assert_eq!(date, Date::new(2023, 1, 15).unwrap());
+    assert_eq!(amount, Currency::from(('$', 123_456)));
+    assert_eq!(swift::parse_currency_amount(field(":33B:")), Ok(amount));
+    assert_eq!(swift::format_32a(date, amount).unwrap(), field(":32A:"));
+}